@@ -2,3 +2,32 @@ use anchor_lang::prelude::*;
 
 #[constant]
 pub const ANCHOR_DISCRIMINATOR: u8 = 8;
+
+/// Maximum number of extra offered (and, separately, wanted) tokens in a basket offer
+#[constant]
+pub const MAX_BASKET_LEGS: usize = 4;
+
+/// Maximum number of alternative mints a taker can pay an offer with, besides `token_mint_b`
+#[constant]
+pub const MAX_PAYMENT_OPTIONS: usize = 4;
+
+/// Current layout version of `Offer` accounts
+#[constant]
+pub const OFFER_VERSION: u8 = 2;
+
+/// Bytes reserved at the end of `Offer` accounts for future fields. Fields added
+/// since `OFFER_VERSION` 2 take their space from here, so the account size stays the same.
+#[constant]
+pub const OFFER_RESERVED_BYTES: usize = 31;
+
+/// Maximum number of attestation authorities in the registry
+#[constant]
+pub const MAX_ATTESTATION_AUTHORITIES: usize = 8;
+
+/// Maximum number of milestones in a milestone escrow
+#[constant]
+pub const MAX_MILESTONES: usize = 8;
+
+/// Basis points in a whole (100%)
+#[constant]
+pub const MAX_BPS: u16 = 10_000;
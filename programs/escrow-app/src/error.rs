@@ -4,4 +4,164 @@ use anchor_lang::prelude::*;
 pub enum ErrorCode {
     #[msg("Maker itself can not take the offer")]
     TakerShouldNotBeMaker,
+    #[msg("Signer is neither the owner nor an active manager of the offer")]
+    NotOfferAuthority,
+    #[msg("Manager expiry must be in the future")]
+    InvalidManagerExpiry,
+    #[msg("Signed order was made for another program")]
+    OrderProgramMismatch,
+    #[msg("Signed order has expired")]
+    OrderExpired,
+    #[msg("Expected an Ed25519 program instruction right before this one")]
+    MissingEd25519Instruction,
+    #[msg("Ed25519 instruction must carry exactly one signature with inline data")]
+    InvalidEd25519Instruction,
+    #[msg("Ed25519 instruction does not verify the maker's signature over this order")]
+    OrderSignatureMismatch,
+    #[msg("The order nonce can only be closed once the signed order has expired")]
+    OrderNotExpired,
+    #[msg("An account required by the offer's custody mode was not provided")]
+    MissingCustodyAccount,
+    #[msg("This instruction doesn't support the offer's custody mode")]
+    OfferCustodyMismatch,
+    #[msg("The delegate PDA is not approved on the maker's token account")]
+    DelegateNotApproved,
+    #[msg("The maker's allowance to the delegate PDA is lower than the offered amount")]
+    InsufficientAllowance,
+    #[msg("The maker's token account holds less than the offered amount")]
+    InsufficientMakerBalance,
+    #[msg("Mint is not an NFT (supply of 1, 0 decimals and no more can be minted)")]
+    NotAnNft,
+    #[msg("A token metadata account is required to check the NFT's collection")]
+    MissingNftMetadata,
+    #[msg("Token metadata account doesn't belong to the NFT mint")]
+    InvalidNftMetadata,
+    #[msg("NFT is not a verified member of the collection")]
+    NftNotInCollection,
+    #[msg("This instruction doesn't support NFT collection offers or collection bids")]
+    CollectionNotSupported,
+    #[msg("This offer doesn't accept the provided token B mint")]
+    TokenMintBNotAccepted,
+    #[msg("Basket already holds the maximum number of tokens on this side")]
+    BasketFull,
+    #[msg("Token is already part of this offer")]
+    DuplicateBasketMint,
+    #[msg("Basket leg amount must be greater than zero")]
+    InvalidBasketAmount,
+    #[msg("Remaining accounts don't match the offer's basket")]
+    InvalidBasketAccounts,
+    #[msg("Payer must be the offer's rent payer")]
+    RentPayerMismatch,
+    #[msg("This instruction doesn't support basket offers")]
+    BasketNotSupported,
+    #[msg("Basket legs must use the token program of the offer's token A")]
+    BasketTokenProgramMismatch,
+    #[msg("Offer already accepts the maximum number of payment mints")]
+    TooManyPaymentOptions,
+    #[msg("Offer already accepts this payment mint")]
+    DuplicatePaymentMint,
+    #[msg("Payment amount must be greater than zero")]
+    InvalidPaymentAmount,
+    #[msg("Collection bids can't accept other payment mints")]
+    PaymentOptionsNotSupported,
+    #[msg("This instruction doesn't support offers accepting other payment mints")]
+    OtherPaymentMintsNotSupported,
+    #[msg("Provide either the payout token account or the claim accounts, not both")]
+    InvalidSettlementAccounts,
+    #[msg("Provide either the associated token account or another token account, not both")]
+    InvalidTokenAccounts,
+    #[msg("Preimage doesn't match the offer's hashlock")]
+    InvalidPreimage,
+    #[msg("HTLC expiry must be in the future")]
+    InvalidHtlcExpiry,
+    #[msg("HTLC has expired and can only be refunded")]
+    HtlcExpired,
+    #[msg("HTLC can't be refunded before it expires")]
+    HtlcNotExpired,
+    #[msg("Signer is not the program admin")]
+    NotProgramAdmin,
+    #[msg("Registry already holds the maximum number of attestation authorities")]
+    TooManyAttestationAuthorities,
+    #[msg("Signer is not a registered attestation authority")]
+    UnknownAttestationAuthority,
+    #[msg("Attestation expiry must be in the future")]
+    InvalidAttestationExpiry,
+    #[msg("This offer requires the taker to present an attestation")]
+    MissingAttestation,
+    #[msg("Attestation is not for the taker, expired, or from an unregistered authority")]
+    InvalidAttestation,
+    #[msg("Taker's attestation tier is below the offer's minimum")]
+    AttestationTierTooLow,
+    #[msg("Offer terms can only be set in the transaction that makes the offer")]
+    OfferTermsLocked,
+    #[msg("This offer requires the taker to present a token account of the gate token")]
+    MissingGateTokenAccount,
+    #[msg("Taker doesn't hold enough of the offer's gate token")]
+    TokenGateNotMet,
+    #[msg("Vesting needs a positive duration and a cliff within it")]
+    InvalidVestingSchedule,
+    #[msg("This offer vests; the vesting and vesting vault accounts are required")]
+    MissingVestingAccounts,
+    #[msg("Nothing has been unlocked since the last claim")]
+    NothingToClaim,
+    #[msg("Arbitration needs a fee of at most 100%, a positive timeout and an arbiter other than the maker")]
+    InvalidArbitrationTerms,
+    #[msg("This offer is arbitrated; the arbitration and arbitration vault accounts are required")]
+    MissingArbitrationAccounts,
+    #[msg("Signer can't do this on the arbitration")]
+    NotArbitrationParty,
+    #[msg("Signer is not the arbiter of this deal")]
+    NotArbiter,
+    #[msg("Arbitration is already disputed")]
+    AlreadyDisputed,
+    #[msg("Arbitration is not disputed")]
+    ArbitrationNotDisputed,
+    #[msg("Arbitration deadline has passed")]
+    ArbitrationExpired,
+    #[msg("Arbitration deadline hasn't passed yet")]
+    ArbitrationNotExpired,
+    #[msg("Maker share must be at most 10000 basis points")]
+    InvalidArbitrationShare,
+    #[msg("The arbiter's token account is required to pay the fee")]
+    MissingArbiterTokenAccount,
+    #[msg("The arbiter of an offer can't take it")]
+    TakerIsArbiter,
+    #[msg("Arbitrated offers can't also vest or have basket legs")]
+    ArbitrationNotSupported,
+    #[msg("Milestone escrows need 1 to MAX_MILESTONES milestones with non-zero amounts")]
+    InvalidMilestones,
+    #[msg("No milestone at this index")]
+    InvalidMilestoneIndex,
+    #[msg("Milestone has already been released")]
+    MilestoneAlreadyReleased,
+    #[msg("Only the maker can release a milestone before it's due")]
+    MilestoneNotDue,
+    #[msg("The maker committed to keep this offer live; it can't be cancelled or replaced yet")]
+    OfferCommitted,
+    #[msg("An offer commitment can only be extended")]
+    CommitmentShortened,
+    #[msg("Minimum fill amount must be between 1 and the offered amount")]
+    InvalidFillPolicy,
+    #[msg("Fill amount must be positive and at most what's left of the offer")]
+    InvalidFillAmount,
+    #[msg("This offer is all-or-nothing; it can only be taken in full")]
+    PartialFillNotAllowed,
+    #[msg("Basket, vesting, arbitrated and NFT offers and collection bids must be taken in full")]
+    PartialFillNotSupported,
+    #[msg("Fill amount is below the offer's minimum fill amount")]
+    FillBelowMinimum,
+    #[msg("Fill would leave less than the minimum fill amount behind")]
+    FillLeavesDust,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Offer doesn't have the old layout; it's already migrated")]
+    OfferAlreadyMigrated,
+    #[msg("Taker doesn't have enough of the token they would pay with")]
+    InsufficientTakerBalance,
+    #[msg("Vault holds nothing beyond what the offer owes its taker")]
+    NothingToSweep,
+    #[msg("Only the current owner of the offer can do this")]
+    NotOfferOwner,
+    #[msg("Non-custodial offers can't be transferred; the tokens stay in the maker's account")]
+    OwnershipNotTransferable,
 }
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+/// Emitted when an offer is taken.
+#[event]
+pub struct OfferTaken {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    /// Who the offer was taken from: the maker, unless it was transferred
+    pub owner: Pubkey,
+    pub taker: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_a_amount: u64,
+    /// What's left of the offer for other takers; zero once completely filled
+    pub token_a_remaining_amount: u64,
+    pub token_mint_b: Pubkey,
+    pub token_b_amount: u64,
+    /// The secret that unlocked a hash-locked offer, so it can be reused to unlock
+    /// a linked contract elsewhere
+    pub preimage: Option<[u8; 32]>,
+}
+
+/// Emitted when the maker commits to keeping an offer live, or extends the commitment.
+#[event]
+pub struct OfferCommitted {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub min_live_until: i64,
+}
+
+/// Emitted when an offer is handed over to a new owner.
+#[event]
+pub struct OfferOwnershipTransferred {
+    pub offer: Pubkey,
+    pub maker: Pubkey,
+    pub previous_owner: Pubkey,
+    pub new_owner: Pubkey,
+}
+
+/// Emitted when an HTLC is claimed, revealing its secret.
+#[event]
+pub struct HtlcClaimed {
+    pub htlc: Pubkey,
+    pub preimage: [u8; 32],
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::MAX_BASKET_LEGS,
+    error::ErrorCode,
+    state::{BasketLeg, Offer},
+    utils::transfer_tokens,
+};
+
+pub fn send_leg_tokens_to_vault(ctx: &Context<AddOfferedLeg>, amount: u64) -> Result<()> {
+    if amount == 0 {
+        return Err(ErrorCode::InvalidBasketAmount.into());
+    }
+
+    if ctx.accounts.offer.basket_offered.len() >= MAX_BASKET_LEGS {
+        return Err(ErrorCode::BasketFull.into());
+    }
+
+    if ctx.accounts.mint.key() == ctx.accounts.offer.token_mint_a {
+        return Err(ErrorCode::DuplicateBasketMint.into());
+    }
+
+    transfer_tokens(
+        &ctx.accounts.owner_token_account,
+        &ctx.accounts.leg_vault,
+        &amount,
+        &ctx.accounts.mint,
+        &ctx.accounts.owner,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn save_offered_leg(ctx: Context<AddOfferedLeg>, amount: u64) -> Result<()> {
+    ctx.accounts.offer.basket_offered.push(BasketLeg {
+        mint: ctx.accounts.mint.key(),
+        amount,
+    });
+    Ok(())
+}
+
+/// The `AddOfferedLeg` struct defines the accounts required to add one more offered
+/// token to an offer, turning it into a basket offer.
+///
+/// The tokens are locked in their own vault, an ATA of the `offer` PDA, just like
+/// the main vault. The maker should send `make_offer` and all the `add_*_leg`
+/// instructions in the same transaction, so nobody can take a half-built basket.
+#[derive(Accounts)]
+pub struct AddOfferedLeg<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred. They
+    /// must sign to move their tokens.
+    pub owner: Signer<'info>,
+
+    /// The account paying for the leg vault. It must be the offer's rent payer,
+    /// since the rent goes back to it once the vault is closed.
+    #[account(mut, address = offer.rent_payer @ ErrorCode::RentPayerMismatch)]
+    pub payer: Signer<'info>,
+
+    /// The token offered by the offer. The leg must belong to its token program,
+    /// which `take_offer` and `cancel_offer` use to move the whole basket.
+    #[account(
+        address = offer.token_mint_a,
+        constraint = *token_mint_a.to_account_info().owner == token_program.key()
+            @ ErrorCode::BasketTokenProgramMismatch,
+    )]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// The extra token being offered
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The owner's token account for `mint`, where the tokens are taken from.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program,
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The offer the leg is added to. The owner and `seeds` constraints are used
+    /// to securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The vault for this leg, owned by the `offer` PDA. `init` fails if the offer
+    /// already holds this token.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program
+    )]
+    pub leg_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create the leg vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create the leg vault.
+    pub system_program: Program<'info, System>,
+}
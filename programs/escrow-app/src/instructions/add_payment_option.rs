@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{
+    constants::MAX_PAYMENT_OPTIONS,
+    error::ErrorCode,
+    state::{Offer, PaymentOption},
+};
+
+pub fn save_payment_option(ctx: Context<AddPaymentOption>, amount: u64) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    let mint = ctx.accounts.mint.key();
+
+    if amount == 0 {
+        return Err(ErrorCode::InvalidPaymentAmount.into());
+    }
+
+    if offer.token_b_collection.is_some() {
+        return Err(ErrorCode::PaymentOptionsNotSupported.into());
+    }
+
+    if offer.payment_options.len() >= MAX_PAYMENT_OPTIONS {
+        return Err(ErrorCode::TooManyPaymentOptions.into());
+    }
+
+    if offer.price_in(&mint).is_some() {
+        return Err(ErrorCode::DuplicatePaymentMint.into());
+    }
+
+    offer.payment_options.push(PaymentOption { mint, amount });
+    Ok(())
+}
+
+/// The `AddPaymentOption` struct defines the accounts required to accept one more
+/// mint as payment for an offer, at its own price (e.g. USDT next to USDC).
+///
+/// The taker picks which of the accepted mints to pay with in `take_offer`.
+#[derive(Accounts)]
+pub struct AddPaymentOption<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred.
+    pub owner: Signer<'info>,
+
+    /// The token accepted as payment
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The offer the payment option is added to. The owner and `seeds` constraints
+    /// are used to securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The Solana Token Program the `mint` belongs to.
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{
+    constants::MAX_BASKET_LEGS,
+    error::ErrorCode,
+    state::{BasketLeg, Offer},
+};
+
+pub fn save_wanted_leg(ctx: Context<AddWantedLeg>, amount: u64) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    let mint = ctx.accounts.mint.key();
+
+    if amount == 0 {
+        return Err(ErrorCode::InvalidBasketAmount.into());
+    }
+
+    if offer.basket_wanted.len() >= MAX_BASKET_LEGS {
+        return Err(ErrorCode::BasketFull.into());
+    }
+
+    if mint == offer.token_mint_b || offer.basket_wanted.iter().any(|leg| leg.mint == mint) {
+        return Err(ErrorCode::DuplicateBasketMint.into());
+    }
+
+    offer.basket_wanted.push(BasketLeg { mint, amount });
+    Ok(())
+}
+
+/// The `AddWantedLeg` struct defines the accounts required to ask for one more
+/// token in return, turning the offer into a basket offer.
+///
+/// The taker has to pay every wanted token in `take_offer`. The maker should send
+/// `make_offer` and all the `add_*_leg` instructions in the same transaction.
+#[derive(Accounts)]
+pub struct AddWantedLeg<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred.
+    pub owner: Signer<'info>,
+
+    /// The token offered by the offer. The leg must belong to its token program,
+    /// which `take_offer` and `cancel_offer` use to move the whole basket.
+    #[account(
+        address = offer.token_mint_a,
+        constraint = *token_mint_a.to_account_info().owner == token_program.key()
+            @ ErrorCode::BasketTokenProgramMismatch,
+    )]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// The extra token wanted in return
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The offer the leg is added to. The owner and `seeds` constraints are used
+    /// to securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The Solana Token Program the `mint` belongs to.
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -1,17 +1,115 @@
 use anchor_lang::prelude::*;
 
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
     token_interface::{
         close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
         TransferChecked,
     },
 };
 
-use crate::state::Offer;
+use crate::{
+    error::ErrorCode,
+    state::{Custody, Offer},
+    utils::{
+        close_token_account, either_token_account, load_basket_leg, transfer_tokens_with_signer,
+    },
+};
+
+pub fn check_offer_authority(ctx: &Context<CancelOffer>) -> Result<()> {
+    if !ctx
+        .accounts
+        .offer
+        .is_authority(ctx.accounts.authority.key, Clock::get()?.unix_timestamp)
+    {
+        return Err(ErrorCode::NotOfferAuthority.into());
+    }
+    Ok(())
+}
+
+pub fn check_offer_commitment(ctx: &Context<CancelOffer>) -> Result<()> {
+    if ctx
+        .accounts
+        .offer
+        .is_committed(Clock::get()?.unix_timestamp)
+    {
+        return Err(ErrorCode::OfferCommitted.into());
+    }
+    Ok(())
+}
+
+/// Returns the extra offered tokens of a basket offer to the owner and closes their
+/// vaults. `remaining_accounts` holds `[mint, leg vault, owner token account]` for
+/// each offered leg.
+pub fn return_basket_legs<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, CancelOffer<'info>>,
+) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+
+    if ctx.remaining_accounts.len() != 3 * offer.basket_offered.len() {
+        return Err(ErrorCode::InvalidBasketAccounts.into());
+    }
+
+    let seeds = [
+        b"offer",
+        ctx.accounts.maker.key.as_ref(),
+        &offer.id.to_le_bytes(),
+        &[offer.bump],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    for (leg, accounts) in offer
+        .basket_offered
+        .iter()
+        .zip(ctx.remaining_accounts.chunks(3))
+    {
+        let leg_accounts = load_basket_leg(accounts, leg)?;
+
+        let leg_vault = get_associated_token_address_with_program_id(
+            &offer.key(),
+            &leg.mint,
+            &ctx.accounts.token_program.key(),
+        );
+        if leg_accounts.from.key() != leg_vault || leg_accounts.to.owner != ctx.accounts.owner.key()
+        {
+            return Err(ErrorCode::InvalidBasketAccounts.into());
+        }
+
+        transfer_tokens_with_signer(
+            &leg_accounts.from,
+            &leg_accounts.to,
+            &leg_accounts.from.amount,
+            &leg_accounts.mint,
+            &offer.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+
+        close_token_account(
+            &leg_accounts.from,
+            &ctx.accounts.rent_payer.to_account_info(),
+            &offer.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    Ok(())
+}
 
 pub fn withdraw_from_vault_and_close_it(ctx: Context<CancelOffer>) -> Result<()> {
-    // Transfer tokens held in vault back to maker's ATA for token_a
+    // Non-custodial offers hold nothing, closing the offer is enough
+    if ctx.accounts.offer.custody == Custody::Delegate {
+        return Ok(());
+    }
+
+    let vault = ctx
+        .accounts
+        .vault
+        .as_mut()
+        .ok_or(ErrorCode::MissingCustodyAccount)?;
+
+    // Transfer tokens held in vault back to the owner's token account for token_a
     let seeds = [
         b"offer",
         ctx.accounts.maker.key.as_ref(),
@@ -20,9 +118,14 @@ pub fn withdraw_from_vault_and_close_it(ctx: Context<CancelOffer>) -> Result<()>
     ];
     let signer_seeds = [&seeds[..]];
 
+    let owner_token_account_a = either_token_account(
+        ctx.accounts.owner_token_account_a.as_deref(),
+        ctx.accounts.other_owner_token_account_a.as_deref(),
+    )?;
+
     let accounts = TransferChecked {
-        from: ctx.accounts.vault.to_account_info(),
-        to: ctx.accounts.maker_token_account_a.to_account_info(),
+        from: vault.to_account_info(),
+        to: owner_token_account_a.to_account_info(),
         mint: ctx.accounts.token_mint_a.to_account_info(),
         authority: ctx.accounts.offer.to_account_info(),
     };
@@ -33,19 +136,19 @@ pub fn withdraw_from_vault_and_close_it(ctx: Context<CancelOffer>) -> Result<()>
         &signer_seeds,
     );
 
-    ctx.accounts.vault.reload()?;
+    vault.reload()?;
 
     transfer_checked(
         cpi_context,
-        ctx.accounts.vault.amount,
+        vault.amount,
         ctx.accounts.token_mint_a.decimals,
     )?;
 
     // Vault can be closed safely now
     let accounts = CloseAccount {
-        account: ctx.accounts.vault.to_account_info(),
+        account: vault.to_account_info(),
         authority: ctx.accounts.offer.to_account_info(),
-        destination: ctx.accounts.maker.to_account_info(),
+        destination: ctx.accounts.rent_payer.to_account_info(),
     };
 
     let cpi_context = CpiContext::new_with_signer(
@@ -59,58 +162,90 @@ pub fn withdraw_from_vault_and_close_it(ctx: Context<CancelOffer>) -> Result<()>
 
 #[derive(Accounts)]
 pub struct CancelOffer<'info> {
-    /// The person created the offer. They must be a `Signer` to authorize the transaction.
-    /// This account will pay for any new accounts created.
+    /// The person cancelling the offer: either its owner or the offer's manager.
+    /// They must be a `Signer` to authorize the transaction.
+    pub authority: Signer<'info>,
+
+    /// The account paying for any new accounts created. It can be the `authority`
+    /// itself or a relayer sponsoring the transaction.
     #[account(mut)]
-    pub maker: Signer<'info>,
+    pub payer: Signer<'info>,
+
+    /// The person who created the offer. Their key is only needed to derive the
+    /// `offer` PDA. The `has_one = maker` constraint on the `offer` account ensures
+    /// this is the correct maker.
+    pub maker: SystemAccount<'info>,
+
+    /// The current owner of the offer: the maker, unless the offer was transferred.
+    /// Tokens from the vault always go back to them, even when a manager cancels.
+    pub owner: SystemAccount<'info>,
+
+    /// Whoever paid the rent for the `offer` and `vault` when the offer was made.
+    /// The rent of the closed accounts is returned to them.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
 
     /// The token the maker was offering; taker will take this token essentially
     #[account(mint::token_program = token_program)]
     pub token_mint_a: InterfaceAccount<'info, Mint>,
 
-    /// The maker's token account for `token_mint_a`. This is where the tokens from
-    /// the vault will be transferred to. `init_if_needed` means Anchor will create
-    /// this account if it doesn't already exist. The `maker` ofcourse pays for the rent.
+    /// The owner's Associated Token Account (ATA) for `token_mint_a`. This is where the
+    /// tokens from the vault will be transferred to. `init_if_needed` means Anchor will
+    /// create this account if it doesn't already exist. The `payer` pays for the rent.
+    /// Leave it out when passing `other_owner_token_account_a`.
     #[account(
         init_if_needed,
-        payer = maker,
+        payer = payer,
         associated_token::mint = token_mint_a,
-        associated_token::authority = maker,
+        associated_token::authority = owner,
         associated_token::token_program = token_program
     )]
-    pub maker_token_account_a: InterfaceAccount<'info, TokenAccount>,
+    pub owner_token_account_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Any other existing token account of the owner for `token_mint_a`, for funds
+    /// that don't live in an ATA. Used instead of `owner_token_account_a`.
+    #[account(
+        mut,
+        token::mint = token_mint_a,
+        token::authority = owner,
+        token::token_program = token_program
+    )]
+    pub other_owner_token_account_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The offer account itself. It is marked `mut` because its state will change,
-    /// and `close` will remove it from the blockchain, returning its rent to the `maker`.
+    /// and `close` will remove it from the blockchain, returning its rent to the `rent_payer`.
     /// The `has_one` and `seeds` constraints are used to securely verify that this
     /// is the correct and valid offer PDA.
     #[account(
         mut,
-        close = maker,
+        close = rent_payer,
         has_one = maker,
+        has_one = rent_payer,
         has_one = token_mint_a,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
         seeds = [b"offer", maker.key().as_ref(), offer.id.to_le_bytes().as_ref()],
         bump = offer.bump,
     )]
     pub offer: Account<'info, Offer>,
 
     /// The vault token account holding the tokens from the maker. This is where
-    /// the tokens will be taken from and returned back to maker.
-    /// It is closed after the transfer, returning its rent to the maker.
+    /// the tokens will be taken from and returned back to the owner.
+    /// It is closed after the transfer, returning its rent to the `rent_payer`.
+    /// Only needed for `Custody::Vault` offers.
     #[account(
         mut,
         associated_token::mint = token_mint_a,
         associated_token::authority = offer,
         associated_token::token_program = token_program
     )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
 
     /// The Solana Token Program. This is required for all token-related operations,
     /// such as transferring tokens.
     pub token_program: Interface<'info, TokenInterface>,
 
     /// The Solana Associated Token Program. This is needed to create new ATAs
-    /// for the `offer` PDA and the `maker_token_account_a`.
+    /// for the `offer` PDA and the `owner_token_account_a`.
     pub associated_token_program: Program<'info, AssociatedToken>,
 
     /// The Solana System Program. This is required to create new accounts (like
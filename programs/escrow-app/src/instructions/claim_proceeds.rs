@@ -0,0 +1,112 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    state::Claim,
+    utils::{close_token_account, transfer_tokens_with_signer},
+};
+
+pub fn withdraw_from_claim_vault_and_close_it(ctx: Context<ClaimProceeds>) -> Result<()> {
+    let seeds = [
+        b"claim",
+        ctx.accounts.claim.offer.as_ref(),
+        &ctx.accounts.claim.fill_index.to_le_bytes(),
+        &[ctx.accounts.claim.bump],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    transfer_tokens_with_signer(
+        &ctx.accounts.claim_vault,
+        &ctx.accounts.payout_token_account,
+        &ctx.accounts.claim_vault.amount,
+        &ctx.accounts.mint,
+        &ctx.accounts.claim.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )?;
+
+    close_token_account(
+        &ctx.accounts.claim_vault,
+        &ctx.accounts.rent_payer.to_account_info(),
+        &ctx.accounts.claim.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )
+}
+
+/// The `ClaimProceeds` struct defines the accounts required to collect the proceeds
+/// of an offer that was taken with pull settlement.
+///
+/// The tokens move from the claim vault to the payout wallet's token account, then
+/// the claim vault and the `claim` account are closed, returning their rent to
+/// whoever paid for them when the offer was taken.
+#[derive(Accounts)]
+pub struct ClaimProceeds<'info> {
+    /// The person who owned the offer when it was taken: the maker, unless the offer
+    /// was transferred. They must sign to release the proceeds.
+    pub maker: Signer<'info>,
+
+    /// The account paying for `payout_token_account` if it has to be created. It can
+    /// be the `maker` itself or a relayer sponsoring the transaction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The owner of the account receiving the proceeds, as set on the offer.
+    /// CHECK: only used as the authority of `payout_token_account`; the
+    /// `has_one = payout` constraint on `claim` ensures it's the right one.
+    pub payout: UncheckedAccount<'info>,
+
+    /// Whoever paid the rent for the `claim` and `claim_vault` when the offer was taken.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// The token the maker was paid with
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The payout wallet's token account for `mint`, where the proceeds go.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = payout,
+        associated_token::token_program = token_program,
+    )]
+    pub payout_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The claim left behind by `take_offer`. `close` returns its rent to the `rent_payer`.
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = maker,
+        has_one = payout,
+        has_one = rent_payer,
+        has_one = mint,
+        seeds = [b"claim", claim.offer.as_ref(), claim.fill_index.to_le_bytes().as_ref()],
+        bump = claim.bump,
+    )]
+    pub claim: Account<'info, Claim>,
+
+    /// The claim vault holding the proceeds, owned by the `claim` PDA.
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = claim,
+        associated_token::token_program = token_program
+    )]
+    pub claim_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create `payout_token_account`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create `payout_token_account`.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::ErrorCode,
+    state::Vesting,
+    utils::{close_token_account, transfer_tokens_with_signer},
+};
+
+pub fn release_unlocked_tokens(ctx: Context<ClaimVested>) -> Result<()> {
+    let vesting = &ctx.accounts.vesting;
+    let now = Clock::get()?.unix_timestamp;
+
+    let seeds = [b"vesting", vesting.offer.as_ref(), &[vesting.bump]];
+    let signer_seeds = [&seeds[..]];
+
+    // Once fully vested, release whatever is left and close the vesting
+    if now >= vesting.end_at {
+        transfer_tokens_with_signer(
+            &ctx.accounts.vesting_vault,
+            &ctx.accounts.beneficiary_token_account,
+            &ctx.accounts.vesting_vault.amount,
+            &ctx.accounts.token_mint,
+            &vesting.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+
+        close_token_account(
+            &ctx.accounts.vesting_vault,
+            &ctx.accounts.rent_payer.to_account_info(),
+            &vesting.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+
+        return vesting.close(ctx.accounts.rent_payer.to_account_info());
+    }
+
+    let amount = vesting
+        .unlocked_amount(now)
+        .ok_or(ErrorCode::MathOverflow)?
+        - vesting.claimed_amount;
+    if amount == 0 {
+        return Err(ErrorCode::NothingToClaim.into());
+    }
+
+    transfer_tokens_with_signer(
+        &ctx.accounts.vesting_vault,
+        &ctx.accounts.beneficiary_token_account,
+        &amount,
+        &ctx.accounts.token_mint,
+        &vesting.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )?;
+
+    ctx.accounts.vesting.claimed_amount += amount;
+    Ok(())
+}
+
+/// The `ClaimVested` struct defines the accounts required to release the unlocked
+/// part of a vesting to its beneficiary.
+///
+/// Anyone can submit the claim, but the tokens always go to the `beneficiary`. The
+/// last claim, after the end of the schedule, closes the vesting and its vault.
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    /// The account paying for the transaction and for `beneficiary_token_account`
+    /// if it has to be created.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The person the tokens vest for.
+    /// CHECK: only used as the authority of `beneficiary_token_account`; the
+    /// `has_one = beneficiary` constraint on `vesting` ensures it's the right one.
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// Whoever paid the rent for the `vesting` and `vesting_vault` when the offer was taken.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// The vesting token
+    #[account(mint::token_program = token_program)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The beneficiary's token account for `token_mint`, where the tokens go.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The vesting left behind by `take_offer`.
+    #[account(
+        mut,
+        has_one = beneficiary,
+        has_one = rent_payer,
+        has_one = token_mint,
+        seeds = [b"vesting", vesting.offer.as_ref()],
+        bump = vesting.bump,
+    )]
+    pub vesting: Account<'info, Vesting>,
+
+    /// The vault holding the vesting tokens, owned by the `vesting` PDA.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create `beneficiary_token_account`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create `beneficiary_token_account`.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::OrderNonce};
+
+/// Checks that the signed order behind `order_nonce` has expired. Before that, the
+/// nonce is what stops the order from being filled a second time.
+pub fn check_order_expired(ctx: &Context<CloseOrderNonce>) -> Result<()> {
+    if Clock::get()?.unix_timestamp < ctx.accounts.order_nonce.expires_at {
+        return Err(ErrorCode::OrderNotExpired.into());
+    }
+    Ok(())
+}
+
+/// The `CloseOrderNonce` struct defines the accounts required to close the nonce of
+/// a filled signed order once the order has expired.
+///
+/// Anyone can call it; the `order_nonce` account is closed, returning its rent to
+/// whoever paid for it when the order was filled.
+#[derive(Accounts)]
+pub struct CloseOrderNonce<'info> {
+    /// Whoever paid the rent for the `order_nonce` when the order was filled.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// The nonce of the filled order.
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        seeds = [
+            b"nonce",
+            order_nonce.maker.as_ref(),
+            order_nonce.nonce.to_le_bytes().as_ref()
+        ],
+        bump = order_nonce.bump,
+    )]
+    pub order_nonce: Account<'info, OrderNonce>,
+}
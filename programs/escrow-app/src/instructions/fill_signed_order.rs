@@ -0,0 +1,194 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR,
+    error::ErrorCode,
+    state::{OrderNonce, SignedOrder},
+    utils::{
+        check_delegate_allowance, transfer_tokens, transfer_tokens_with_signer,
+        verify_ed25519_instruction,
+    },
+};
+
+pub fn verify_signed_order(ctx: &Context<FillSignedOrder>, order: &SignedOrder) -> Result<()> {
+    if ctx.accounts.taker.key() == order.maker {
+        return Err(ErrorCode::TakerShouldNotBeMaker.into());
+    }
+
+    if order.program_id != crate::ID {
+        return Err(ErrorCode::OrderProgramMismatch.into());
+    }
+
+    if Clock::get()?.unix_timestamp >= order.expires_at {
+        return Err(ErrorCode::OrderExpired.into());
+    }
+
+    verify_ed25519_instruction(
+        &ctx.accounts.instructions.to_account_info(),
+        &order.maker,
+        &order.message()?,
+    )?;
+
+    check_delegate_allowance(
+        &ctx.accounts.maker_token_account_a,
+        &ctx.accounts.delegate.key(),
+        order.token_a_amount,
+    )
+}
+
+pub fn send_tokens_from_taker_to_maker(
+    ctx: &Context<FillSignedOrder>,
+    order: &SignedOrder,
+) -> Result<()> {
+    transfer_tokens(
+        &ctx.accounts.taker_token_account_b,
+        &ctx.accounts.maker_token_account_b,
+        &order.token_b_amount,
+        &ctx.accounts.token_mint_b,
+        &ctx.accounts.taker,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn pull_tokens_from_maker_to_taker(
+    ctx: Context<FillSignedOrder>,
+    order: &SignedOrder,
+) -> Result<()> {
+    // The maker approved the delegate PDA on their token account, so the program
+    // can move the tokens without the maker signing this transaction
+    let seeds = [
+        b"delegate",
+        ctx.accounts.maker.key.as_ref(),
+        &[ctx.bumps.delegate],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    transfer_tokens_with_signer(
+        &ctx.accounts.maker_token_account_a,
+        &ctx.accounts.taker_token_account_a,
+        &order.token_a_amount,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.delegate.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )?;
+
+    // Burn the nonce so the same order can't be filled again
+    ctx.accounts.order_nonce.set_inner(OrderNonce {
+        maker: order.maker,
+        nonce: order.nonce,
+        expires_at: order.expires_at,
+        rent_payer: ctx.accounts.payer.key(),
+        bump: ctx.bumps.order_nonce,
+    });
+    Ok(())
+}
+
+/// The `FillSignedOrder` struct defines the accounts required to fill an order the
+/// maker signed off-chain.
+///
+/// The maker's signature is checked through the instructions sysvar against an
+/// Ed25519 program instruction placed right before this one. Token A is pulled
+/// from the maker's account by the `delegate` PDA, which the maker must have
+/// approved beforehand for at least the order's amount; the allowance is shared
+/// with the maker's approval offers. Each order nonce can only be used once.
+#[derive(Accounts)]
+#[instruction(order: SignedOrder)]
+pub struct FillSignedOrder<'info> {
+    /// The person filling the order. They must be a `Signer` to authorize paying
+    /// the maker with their tokens.
+    pub taker: Signer<'info>,
+
+    /// The account paying for any new accounts created. It can be the `taker` itself
+    /// or a relayer submitting the order.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The person who signed the order. They don't sign this transaction.
+    #[account(address = order.maker)]
+    pub maker: SystemAccount<'info>,
+
+    /// The token the maker is selling
+    #[account(address = order.token_mint_a, mint::token_program = token_program)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token the maker wants in return
+    #[account(address = order.token_mint_b, mint::token_program = token_program)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The program-owned PDA the maker approved as delegate on `maker_token_account_a`.
+    /// CHECK: only used as a signer through its seeds; it holds no data.
+    #[account(seeds = [b"delegate", maker.key().as_ref()], bump)]
+    pub delegate: UncheckedAccount<'info>,
+
+    /// The maker's token account for `token_mint_a`. Tokens are pulled from here by
+    /// the `delegate`.
+    #[account(
+        mut,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The taker's token account for `token_mint_a`, created if it doesn't exist.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The taker's token account for `token_mint_b`, used to pay the maker.
+    #[account(
+        mut,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_token_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The maker's token account for `token_mint_b`, created if it doesn't exist.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_token_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Marks `order.nonce` as used. `init` fails if the order was already filled.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + OrderNonce::INIT_SPACE,
+        seeds = [b"nonce", maker.key().as_ref(), order.nonce.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub order_nonce: Account<'info, OrderNonce>,
+
+    /// The instructions sysvar, used to look at the Ed25519 instruction.
+    /// CHECK: the address is checked against the sysvar id.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create new ATAs
+    /// for the taker and the maker.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create new accounts (like
+    /// the `order_nonce` account).
+    pub system_program: Program<'info, System>,
+}
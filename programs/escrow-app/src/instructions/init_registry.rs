@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR, error::ErrorCode, program::EscrowApp, state::Registry,
+};
+
+pub fn save_registry(ctx: Context<InitRegistry>) -> Result<()> {
+    ctx.accounts.registry.set_inner(Registry {
+        admin: *ctx.accounts.admin.key,
+        authorities: Vec::new(),
+        bump: ctx.bumps.registry,
+    });
+    Ok(())
+}
+
+/// The `InitRegistry` struct defines the accounts required to create the attestation
+/// authority registry.
+///
+/// There is one registry for the whole program. It can only be created by the
+/// program's upgrade authority, who becomes its admin.
+#[derive(Accounts)]
+pub struct InitRegistry<'info> {
+    /// The program's upgrade authority. It becomes the registry's admin.
+    pub admin: Signer<'info>,
+
+    /// The account paying for the `registry` account. It can be the `admin` itself
+    /// or another wallet funding the setup.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// This account will be created by the instruction to store the authorities.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Registry::INIT_SPACE,
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    /// This program, to look up its program data account.
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, EscrowApp>,
+
+    /// The program data account, holding the upgrade authority.
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorCode::NotProgramAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// The Solana System Program. This is required to create the `registry` account.
+    pub system_program: Program<'info, System>,
+}
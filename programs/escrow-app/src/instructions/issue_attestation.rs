@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR,
+    error::ErrorCode,
+    state::{Attestation, Registry},
+};
+
+pub fn save_attestation(ctx: Context<IssueAttestation>, tier: u8, expires_at: i64) -> Result<()> {
+    if expires_at <= Clock::get()?.unix_timestamp {
+        return Err(ErrorCode::InvalidAttestationExpiry.into());
+    }
+
+    // Re-issuing doesn't change who paid for the account
+    let rent_payer = match ctx.accounts.attestation.rent_payer {
+        rent_payer if rent_payer != Pubkey::default() => rent_payer,
+        _ => *ctx.accounts.payer.key,
+    };
+
+    ctx.accounts.attestation.set_inner(Attestation {
+        authority: *ctx.accounts.authority.key,
+        wallet: ctx.accounts.wallet.key(),
+        tier,
+        expires_at,
+        rent_payer,
+        bump: ctx.bumps.attestation,
+    });
+    Ok(())
+}
+
+/// The `IssueAttestation` struct defines the accounts required for an attestation
+/// authority to vouch for a wallet.
+///
+/// Issuing again for the same wallet updates its tier and expiry.
+#[derive(Accounts)]
+pub struct IssueAttestation<'info> {
+    /// One of the registry's attestation authorities.
+    pub authority: Signer<'info>,
+
+    /// The account paying for the `attestation` account. The rent goes back to it once
+    /// the attestation is revoked.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The wallet being attested.
+    /// CHECK: only used as a seed and stored on the attestation.
+    pub wallet: UncheckedAccount<'info>,
+
+    /// The attestation authority registry.
+    #[account(
+        constraint = registry.authorities.contains(authority.key)
+            @ ErrorCode::UnknownAttestationAuthority,
+        seeds = [b"registry"],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, Registry>,
+
+    /// The attestation, created if it doesn't exist yet.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Attestation::INIT_SPACE,
+        seeds = [b"attestation", authority.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    /// The Solana System Program. This is required to create the `attestation` account.
+    pub system_program: Program<'info, System>,
+}
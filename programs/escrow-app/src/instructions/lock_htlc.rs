@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR, error::ErrorCode, state::Htlc, utils::transfer_tokens,
+};
+
+pub fn send_locked_tokens_to_vault(
+    ctx: &Context<LockHtlc>,
+    amount: u64,
+    expires_at: i64,
+) -> Result<()> {
+    if expires_at <= Clock::get()?.unix_timestamp {
+        return Err(ErrorCode::InvalidHtlcExpiry.into());
+    }
+
+    transfer_tokens(
+        &ctx.accounts.maker_token_account,
+        &ctx.accounts.vault,
+        &amount,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.maker,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn save_htlc(
+    ctx: Context<LockHtlc>,
+    id: u64,
+    counterparty: Pubkey,
+    amount: u64,
+    hashlock: [u8; 32],
+    expires_at: i64,
+) -> Result<()> {
+    ctx.accounts.htlc.set_inner(Htlc {
+        id,
+        maker: *ctx.accounts.maker.key,
+        counterparty,
+        token_mint: ctx.accounts.token_mint.key(),
+        amount,
+        hashlock,
+        expires_at,
+        rent_payer: *ctx.accounts.payer.key,
+        bump: ctx.bumps.htlc,
+    });
+    Ok(())
+}
+
+/// The `LockHtlc` struct defines the accounts required to lock tokens in a
+/// hash-time-locked contract, e.g. one side of a cross-chain atomic swap.
+///
+/// It follows the `MakeOffer` pattern: a PDA stores the terms and a vault, an ATA
+/// of that PDA, holds the tokens until they are claimed or refunded.
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct LockHtlc<'info> {
+    /// The person locking the tokens. They must sign to authorize moving their
+    /// tokens into the vault.
+    pub maker: Signer<'info>,
+
+    /// The account paying for the new accounts. It can be the `maker` itself or a
+    /// relayer sponsoring the transaction; the rent goes back to it once the HTLC is closed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The token being locked
+    #[account(mint::token_program = token_program)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The maker's token account for `token_mint`, where the tokens are taken from.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = maker,
+        token::token_program = token_program,
+    )]
+    pub maker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// This account will be created by the instruction to store the HTLC terms.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Htlc::INIT_SPACE,
+        seeds = [b"htlc", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub htlc: Account<'info, Htlc>,
+
+    /// The vault holding the locked tokens, owned by the `htlc` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = htlc,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create the vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create new accounts (like
+    /// the `htlc` and `vault` accounts).
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, MAX_MILESTONES},
+    error::ErrorCode,
+    state::{Milestone, MilestoneEscrow, MilestoneTerms},
+    utils::transfer_tokens,
+};
+
+pub fn send_locked_tokens_to_vault(
+    ctx: &Context<LockMilestones>,
+    milestones: &[MilestoneTerms],
+) -> Result<()> {
+    if milestones.is_empty() || milestones.len() > MAX_MILESTONES {
+        return Err(ErrorCode::InvalidMilestones.into());
+    }
+
+    let mut total: u64 = 0;
+    for milestone in milestones {
+        if milestone.amount == 0 {
+            return Err(ErrorCode::InvalidMilestones.into());
+        }
+
+        total = total
+            .checked_add(milestone.amount)
+            .ok_or(ErrorCode::InvalidMilestones)?;
+    }
+
+    transfer_tokens(
+        &ctx.accounts.maker_token_account,
+        &ctx.accounts.vault,
+        &total,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.maker,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn save_milestone_escrow(
+    ctx: Context<LockMilestones>,
+    id: u64,
+    beneficiary: Pubkey,
+    milestones: Vec<MilestoneTerms>,
+) -> Result<()> {
+    ctx.accounts.escrow.set_inner(MilestoneEscrow {
+        id,
+        maker: *ctx.accounts.maker.key,
+        beneficiary,
+        token_mint: ctx.accounts.token_mint.key(),
+        milestones: milestones
+            .into_iter()
+            .map(|terms| Milestone {
+                amount: terms.amount,
+                release_at: terms.release_at,
+                released: false,
+            })
+            .collect(),
+        rent_payer: *ctx.accounts.payer.key,
+        bump: ctx.bumps.escrow,
+    });
+    Ok(())
+}
+
+/// The `LockMilestones` struct defines the accounts required to lock tokens for a
+/// beneficiary, to be released in stages.
+///
+/// It follows the `LockHtlc` pattern: a PDA stores the milestones and a vault, an
+/// ATA of that PDA, holds the sum of their amounts.
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct LockMilestones<'info> {
+    /// The person locking the tokens. They must sign to authorize moving their
+    /// tokens into the vault.
+    pub maker: Signer<'info>,
+
+    /// The account paying for the new accounts. It can be the `maker` itself or a
+    /// relayer sponsoring the transaction; the rent goes back to it once the escrow is closed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The token being locked
+    #[account(mint::token_program = token_program)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The maker's token account for `token_mint`, where the tokens are taken from.
+    #[account(
+        mut,
+        token::mint = token_mint,
+        token::authority = maker,
+        token::token_program = token_program,
+    )]
+    pub maker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// This account will be created by the instruction to store the milestones.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + MilestoneEscrow::INIT_SPACE,
+        seeds = [b"milestones", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, MilestoneEscrow>,
+
+    /// The vault holding the locked tokens, owned by the `escrow` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create the vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create new accounts (like
+    /// the `escrow` and `vault` accounts).
+    pub system_program: Program<'info, System>,
+}
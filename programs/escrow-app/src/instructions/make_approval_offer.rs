@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR,
+    state::{Custody, Offer},
+    utils::check_delegate_allowance,
+};
+
+pub fn save_approval_offer(
+    ctx: Context<MakeApprovalOffer>,
+    offer_id: u64,
+    token_a_offered_amount: u64,
+    token_b_amount_wanted: u64,
+) -> Result<()> {
+    // An offer the delegate can't fill would only fail its takers
+    check_delegate_allowance(
+        &ctx.accounts.maker_token_account_a,
+        &ctx.accounts.delegate.key(),
+        token_a_offered_amount,
+    )?;
+
+    ctx.accounts.offer.set_inner(Offer {
+        token_mint_a: ctx.accounts.token_mint_a.key(),
+        token_mint_b: ctx.accounts.token_mint_b.key(),
+        token_b_amount_wanted,
+        custody: Custody::Delegate,
+        token_a_offered_amount,
+        ..Offer::new(
+            offer_id,
+            *ctx.accounts.maker.key,
+            *ctx.accounts.payer.key,
+            ctx.bumps.offer,
+        )
+    });
+    Ok(())
+}
+
+/// The `MakeApprovalOffer` struct defines the accounts required to make a
+/// non-custodial offer.
+///
+/// Instead of depositing into a vault, the maker keeps the offered tokens in their
+/// own token account and approves the program's `delegate` PDA (seeds
+/// `["delegate", maker]`) on it, for at least the offered amount. A token account
+/// has a single delegate and a single allowance, so that allowance backs every
+/// approval offer and signed order of the maker on this token: each `approve` must
+/// cover all of them together, as it replaces the previous one. The allowance is
+/// checked here and again on every fill, which fails cleanly if it was lowered.
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct MakeApprovalOffer<'info> {
+    /// The person making the offer. They must sign the transaction.
+    pub maker: Signer<'info>,
+
+    /// The account paying for the `offer` account. It can be the `maker` itself or a
+    /// relayer sponsoring the transaction; the rent goes back to it once the offer is closed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The token the maker is offering
+    #[account(mint::token_program = token_program)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// The token the maker want in return.
+    #[account(mint::token_program = token_program)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// The maker's token account for `token_mint_a`. The tokens stay here until the
+    /// offer is taken.
+    #[account(
+        associated_token::mint = token_mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_token_account_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// The program-owned PDA the maker approved as delegate on `maker_token_account_a`.
+    /// CHECK: only compared with the token account's delegate; it holds no data.
+    #[account(seeds = [b"delegate", maker.key().as_ref()], bump)]
+    pub delegate: UncheckedAccount<'info>,
+
+    /// This account will be created by the instruction to store the offer details.
+    /// It uses the same seeds as the offers made through `make_offer`.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Offer::INIT_SPACE,
+        seeds = [b"offer", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to check the
+    /// `maker_token_account_a`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create the `offer` account.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{constants::ANCHOR_DISCRIMINATOR, state::Offer, utils::transfer_tokens};
+
+pub fn send_offered_tokens_to_vault(
+    ctx: &Context<MakeCollectionBid>,
+    token_a_offered_amount: u64,
+) -> Result<()> {
+    transfer_tokens(
+        &ctx.accounts.maker_token_account_a,
+        &ctx.accounts.vault,
+        &token_a_offered_amount,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.maker,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn save_collection_bid(
+    ctx: Context<MakeCollectionBid>,
+    offer_id: u64,
+    token_a_offered_amount: u64,
+    collection: Pubkey,
+) -> Result<()> {
+    ctx.accounts.offer.set_inner(Offer {
+        token_mint_a: ctx.accounts.token_mint_a.key(),
+        // The taker chooses which NFT of the collection to deliver
+        token_mint_b: Pubkey::default(),
+        token_b_amount_wanted: 1,
+        token_a_offered_amount,
+        token_b_collection: Some(collection),
+        ..Offer::new(
+            offer_id,
+            *ctx.accounts.maker.key,
+            *ctx.accounts.payer.key,
+            ctx.bumps.offer,
+        )
+    });
+    Ok(())
+}
+
+/// The `MakeCollectionBid` struct defines the accounts required to bid for any
+/// NFT of a collection.
+///
+/// The maker locks `token_mint_a` in a vault like in `MakeOffer`. There is no
+/// `token_mint_b`: the taker picks any NFT that is a verified member of the
+/// collection and passes its mint and token metadata to `take_offer`.
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct MakeCollectionBid<'info> {
+    /// The person making the bid. They must sign the transaction to authorize
+    /// moving their tokens into the vault.
+    pub maker: Signer<'info>,
+
+    /// The account paying for the new accounts. It can be the `maker` itself or a
+    /// relayer sponsoring the transaction; the rent goes back to it once the offer is closed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The token the maker is offering for the NFT
+    #[account(mint::token_program = token_program)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// The maker's Associated Token Account (ATA) for `token_mint_a`.
+    #[account(
+        mut,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_token_account_a: InterfaceAccount<'info, TokenAccount>,
+
+    /// This account will be created by the instruction to store the bid details.
+    /// It uses the same seeds as the offers made through `make_offer`.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Offer::INIT_SPACE,
+        seeds = [b"offer", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The vault holding the offered tokens, owned by the `offer` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create the vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create new accounts (like
+    /// the `offer` and `vault` accounts).
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    metadata::MetadataAccount,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR,
+    state::Offer,
+    utils::{check_nft_mint, check_verified_collection, transfer_tokens},
+};
+
+pub fn send_nft_to_vault(ctx: &Context<MakeNftOffer>, collection: Option<Pubkey>) -> Result<()> {
+    check_nft_mint(&ctx.accounts.token_mint_a)?;
+
+    if let Some(collection) = collection {
+        check_verified_collection(
+            ctx.accounts.token_a_metadata.as_deref(),
+            &ctx.accounts.token_mint_a.key(),
+            &collection,
+        )?;
+    }
+
+    transfer_tokens(
+        &ctx.accounts.maker_token_account_a,
+        &ctx.accounts.vault,
+        &1,
+        &ctx.accounts.token_mint_a,
+        &ctx.accounts.maker,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn save_nft_offer(
+    ctx: Context<MakeNftOffer>,
+    offer_id: u64,
+    token_b_amount_wanted: u64,
+    collection: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.offer.set_inner(Offer {
+        token_mint_a: ctx.accounts.token_mint_a.key(),
+        token_mint_b: ctx.accounts.token_mint_b.key(),
+        token_b_amount_wanted,
+        token_a_offered_amount: 1,
+        token_a_collection: collection,
+        ..Offer::new(
+            offer_id,
+            *ctx.accounts.maker.key,
+            *ctx.accounts.payer.key,
+            ctx.bumps.offer,
+        )
+    });
+    Ok(())
+}
+
+/// The `MakeNftOffer` struct defines the accounts required to offer an NFT.
+///
+/// It works like `MakeOffer`, but `token_mint_a` must be an NFT (a supply of 1 and
+/// 0 decimals) and exactly one token goes into the vault. When a collection is
+/// given, the NFT's token metadata must show it as a verified member of it, and
+/// the collection is stored on the offer for takers to rely on.
+#[derive(Accounts)]
+#[instruction(id: u64)]
+pub struct MakeNftOffer<'info> {
+    /// The person making the offer. They must sign the transaction to authorize
+    /// moving their NFT into the vault.
+    pub maker: Signer<'info>,
+
+    /// The account paying for the new accounts. It can be the `maker` itself or a
+    /// relayer sponsoring the transaction; the rent goes back to it once the offer is closed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The NFT the maker is offering
+    #[account(mint::token_program = token_program)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token the maker want in return.
+    #[account(mint::token_program = token_program)]
+    pub token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token metadata account of `token_mint_a`. Only needed when a collection is given.
+    pub token_a_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    /// The maker's Associated Token Account (ATA) holding the NFT.
+    #[account(
+        mut,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// This account will be created by the instruction to store the offer details.
+    /// It uses the same seeds as the offers made through `make_offer`.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Offer::INIT_SPACE,
+        seeds = [b"offer", maker.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The vault holding the NFT, owned by the `offer` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create the vault.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create new accounts (like
+    /// the `offer` and `vault` accounts).
+    pub system_program: Program<'info, System>,
+}
@@ -24,15 +24,24 @@ pub fn send_offered_tokens_to_vault(
 pub fn save_offer(
     ctx: Context<MakeOffer>,
     offer_id: u64,
+    token_a_offered_amount: u64,
     token_b_amount_wanted: u64,
+    payout: Option<Pubkey>,
+    hashlock: Option<[u8; 32]>,
 ) -> Result<()> {
     ctx.accounts.offer.set_inner(Offer {
-        id: offer_id,
-        maker: *ctx.accounts.maker.key,
         token_mint_a: ctx.accounts.token_mint_a.key(),
         token_mint_b: ctx.accounts.token_mint_b.key(),
         token_b_amount_wanted,
-        bump: ctx.bumps.offer,
+        token_a_offered_amount,
+        payout: payout.unwrap_or(*ctx.accounts.maker.key),
+        hashlock,
+        ..Offer::new(
+            offer_id,
+            *ctx.accounts.maker.key,
+            *ctx.accounts.payer.key,
+            ctx.bumps.offer,
+        )
     });
     Ok(())
 }
@@ -46,10 +55,15 @@ pub fn save_offer(
 #[derive(Accounts)]
 #[instruction(id: u64)] // `id` is a unique number for this specific offer.
 pub struct MakeOffer<'info> {
-    /// The person making the offer. They must sign the transaction and will pay for it.
-    #[account(mut)]
+    /// The person making the offer. They must sign the transaction to authorize
+    /// moving their tokens into the vault.
     pub maker: Signer<'info>,
 
+    /// The account paying for the new accounts. It can be the `maker` itself or a
+    /// relayer sponsoring the transaction; the rent goes back to it once the offer is closed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
     /// The token the maker is offering
     #[account(mint::token_program = token_program)]
     pub token_mint_a: InterfaceAccount<'info, Mint>,
@@ -58,17 +72,18 @@ pub struct MakeOffer<'info> {
     #[account(mint::token_program = token_program)]
     pub token_mint_b: InterfaceAccount<'info, Mint>,
 
-    /// This is the maker's own Associated Token Account (ATA) for `token_mint_a`.
+    /// This is the maker's own token account for `token_mint_a`, usually their
+    /// Associated Token Account (ATA) but any account they own works.
     /// It is marked as `mut` because tokens will be transferred out of it into the vault.
     ///
-    /// `#[account(associated_token::mint = token_mint_a, ...)]`
-    /// These constraints verify that this is indeed the correct ATA for the `maker`
-    /// and `token_mint_a`, ensuring the transaction is acting on the intended account.
+    /// `#[account(token::mint = token_mint_a, ...)]`
+    /// These constraints verify that this account holds `token_mint_a` and belongs
+    /// to the `maker`, ensuring the transaction is acting on the intended account.
     #[account(
         mut,
-        associated_token::mint = token_mint_a,
-        associated_token::authority = maker,
-        associated_token::token_program = token_program,
+        token::mint = token_mint_a,
+        token::authority = maker,
+        token::token_program = token_program,
     )]
     pub maker_token_account_a: InterfaceAccount<'info, TokenAccount>,
 
@@ -78,7 +93,7 @@ pub struct MakeOffer<'info> {
     /// the address is unique for each offer from a specific maker.
     #[account(
         init,
-        payer = maker,
+        payer = payer,
         space = (ANCHOR_DISCRIMINATOR as usize) + Offer::INIT_SPACE,
         seeds = [b"offer", maker.key().as_ref(), id.to_le_bytes().as_ref()],
         bump
@@ -94,7 +109,7 @@ pub struct MakeOffer<'info> {
     /// signing authority) can move them out, securing the escrow.
     #[account(
         init,
-        payer = maker,
+        payer = payer,
         associated_token::mint = token_mint_a,
         associated_token::authority = offer,
         associated_token::token_program = token_program
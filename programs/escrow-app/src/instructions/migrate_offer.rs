@@ -0,0 +1,84 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR,
+    state::{Offer, OfferV1},
+};
+
+pub fn migrate_offer_account(ctx: Context<MigrateOffer>) -> Result<()> {
+    let offer_info = ctx.accounts.offer.to_account_info();
+    let old_offer = OfferV1::try_from_account_data(&offer_info.try_borrow_data()?)?;
+
+    if old_offer.token_mint_a != ctx.accounts.token_mint_a.key() {
+        return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+    }
+
+    let offer = old_offer.into_offer(ctx.accounts.vault.amount);
+
+    // Top up the rent for the bigger account
+    let space = ANCHOR_DISCRIMINATOR as usize + Offer::INIT_SPACE;
+    let rent_delta = Rent::get()?
+        .minimum_balance(space)
+        .saturating_sub(offer_info.lamports());
+
+    if rent_delta > 0 {
+        let accounts = Transfer {
+            from: ctx.accounts.payer.to_account_info(),
+            to: offer_info.clone(),
+        };
+
+        let cpi_context = CpiContext::new(ctx.accounts.system_program.to_account_info(), accounts);
+
+        transfer(cpi_context, rent_delta)?;
+    }
+
+    offer_info.realloc(space, true)?;
+
+    // Rewrite the whole account, so nothing of the old layout is left behind
+    let mut data = offer_info.try_borrow_mut_data()?;
+    data.fill(0);
+    offer.try_serialize(&mut &mut data[..])
+}
+
+/// The `MigrateOffer` struct defines the accounts required to upgrade an offer
+/// created before accounts were versioned to the current `Offer` layout.
+///
+/// The account is grown in place with `realloc`, keeping its address and tokens. Its
+/// fields are carried over as they were, the newer ones get their defaults, and the
+/// offered amount is read from the `vault`. Anyone can migrate an offer: the `payer`,
+/// the maker or not, covers the rent for the extra space.
+#[derive(Accounts)]
+pub struct MigrateOffer<'info> {
+    /// The account paying the rent for the extra space.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The offer to migrate. It can't be an `Account<Offer>` since it doesn't have
+    /// the current layout yet.
+    /// CHECK: owned by this program; the handler checks the discriminator and that
+    /// the size is the one of an old layout offer before reading it.
+    #[account(mut, owner = crate::ID)]
+    pub offer: UncheckedAccount<'info>,
+
+    /// The mint of the offered token; the handler checks it's the offer's `token_mint_a`.
+    #[account(mint::token_program = token_program)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// The vault holding the offered tokens; its balance becomes the offered amount.
+    #[account(
+        associated_token::mint = token_mint_a,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Token Program that owns the `vault`.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana System Program. This is required to transfer the rent delta.
+    pub system_program: Program<'info, System>,
+}
@@ -1,7 +1,73 @@
+pub mod add_offered_leg;
+pub mod add_payment_option;
+pub mod add_wanted_leg;
 pub mod cancel_offer;
+pub mod claim_htlc;
+pub mod claim_proceeds;
+pub mod claim_vested;
+pub mod close_order_nonce;
+pub mod fill_signed_order;
+pub mod init_registry;
+pub mod issue_attestation;
+pub mod lock_htlc;
+pub mod lock_milestones;
+pub mod make_approval_offer;
+pub mod make_collection_bid;
+pub mod make_nft_offer;
 pub mod make_offer;
+pub mod migrate_offer;
+pub mod quote_take;
+pub mod raise_dispute;
+pub mod refund_htlc;
+pub mod release_milestone;
+pub mod replace_offer;
+pub mod revoke_attestation;
+pub mod set_offer_arbitration;
+pub mod set_offer_attestation;
+pub mod set_offer_commitment;
+pub mod set_offer_fill_policy;
+pub mod set_offer_gate;
+pub mod set_offer_manager;
+pub mod set_offer_vesting;
+pub mod set_registry_authorities;
+pub mod settle_arbitration;
+pub mod sweep;
 pub mod take_offer;
+pub mod transfer_offer_ownership;
 
+pub use add_offered_leg::*;
+pub use add_payment_option::*;
+pub use add_wanted_leg::*;
 pub use cancel_offer::*;
+pub use claim_htlc::*;
+pub use claim_proceeds::*;
+pub use claim_vested::*;
+pub use close_order_nonce::*;
+pub use fill_signed_order::*;
+pub use init_registry::*;
+pub use issue_attestation::*;
+pub use lock_htlc::*;
+pub use lock_milestones::*;
+pub use make_approval_offer::*;
+pub use make_collection_bid::*;
+pub use make_nft_offer::*;
 pub use make_offer::*;
+pub use migrate_offer::*;
+pub use quote_take::*;
+pub use raise_dispute::*;
+pub use refund_htlc::*;
+pub use release_milestone::*;
+pub use replace_offer::*;
+pub use revoke_attestation::*;
+pub use set_offer_arbitration::*;
+pub use set_offer_attestation::*;
+pub use set_offer_commitment::*;
+pub use set_offer_fill_policy::*;
+pub use set_offer_gate::*;
+pub use set_offer_manager::*;
+pub use set_offer_vesting::*;
+pub use set_registry_authorities::*;
+pub use settle_arbitration::*;
+pub use sweep::*;
 pub use take_offer::*;
+pub use transfer_offer_ownership::*;
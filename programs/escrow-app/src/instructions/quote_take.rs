@@ -0,0 +1,208 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    metadata::MetadataAccount,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::MAX_BPS,
+    error::ErrorCode,
+    instructions::take_offer::{validate_take, TakeAccounts},
+    math::{checked_mul_div, Rounding},
+    state::{Attestation, BasketLeg, Offer, Registry},
+};
+
+/// What a take would exchange, as returned by `quote_take`. When the take would fail,
+/// `error` holds its error code and the amounts are zero.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct TakeQuote {
+    /// Token A the recipient would get
+    pub token_a_amount: u64,
+    /// Token B the taker would pay
+    pub token_b_amount: u64,
+    /// The most the arbiter could take from the payment, for arbitrated offers
+    pub arbiter_fee: u64,
+    /// What would be left of the offer for other takers
+    pub token_a_remaining_amount: u64,
+    /// The basket legs the taker would pay, on top of token B
+    pub basket_wanted: Vec<BasketLeg>,
+    /// The basket legs the recipient would get, on top of token A
+    pub basket_offered: Vec<BasketLeg>,
+    /// The error code the take would fail with, if any
+    pub error: Option<u32>,
+}
+
+pub fn quote<'info>(
+    ctx: Context<'_, '_, 'info, 'info, QuoteTake<'info>>,
+    preimage: Option<[u8; 32]>,
+    token_a_amount: Option<u64>,
+) -> Result<TakeQuote> {
+    let quote = match check_quote(&ctx, preimage, token_a_amount) {
+        Ok(quote) => quote,
+        Err(error) => TakeQuote {
+            error: Some(error_code(error)),
+            ..TakeQuote::default()
+        },
+    };
+    Ok(quote)
+}
+
+fn check_quote<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, QuoteTake<'info>>,
+    preimage: Option<[u8; 32]>,
+    token_a_amount: Option<u64>,
+) -> Result<TakeQuote> {
+    let accounts = &ctx.accounts;
+    let offer = &accounts.offer;
+
+    let take_accounts = TakeAccounts {
+        offer,
+        taker: accounts.taker.key(),
+        recipient: accounts.recipient.key(),
+        payout: accounts.payout.key(),
+        rent_payer: accounts.rent_payer.key(),
+        token_mint_b: &accounts.token_mint_b,
+        token_b_metadata: accounts.token_b_metadata.as_deref(),
+        taker_token_account_b: accounts.taker_token_account_b.as_deref(),
+        maker_token_account_a: accounts.maker_token_account_a.as_deref(),
+        delegate: accounts.delegate.as_ref().map(|delegate| delegate.key()),
+        registry: accounts.registry.as_deref(),
+        attestation: accounts.attestation.as_deref(),
+        gate_token_account: accounts.gate_token_account.as_deref(),
+        token_program: accounts.token_program.key(),
+    };
+
+    let fill = validate_take(
+        &take_accounts,
+        ctx.remaining_accounts,
+        preimage,
+        token_a_amount,
+    )?;
+
+    let arbiter_fee = match offer.arbitration {
+        Some(terms) => checked_mul_div(
+            fill.token_b_amount,
+            terms.fee_bps as u64,
+            MAX_BPS as u64,
+            Rounding::Down,
+        )
+        .ok_or(ErrorCode::MathOverflow)?,
+        None => 0,
+    };
+
+    Ok(TakeQuote {
+        token_a_amount: fill.token_a_amount,
+        token_b_amount: fill.token_b_amount,
+        arbiter_fee,
+        token_a_remaining_amount: offer.token_a_remaining_amount() - fill.token_a_amount,
+        basket_wanted: fill.basket_wanted,
+        basket_offered: fill.basket_offered,
+        error: None,
+    })
+}
+
+fn error_code(error: Error) -> u32 {
+    match error {
+        Error::AnchorError(error) => error.error_code_number,
+        Error::ProgramError(error) => u64::from(error.program_error) as u32,
+    }
+}
+
+/// The `QuoteTake` struct defines the accounts required to quote a take of an offer
+/// without taking it.
+///
+/// It's meant to be simulated: it runs the checks of `take_offer` and returns the
+/// amounts the take would exchange, or the error it would fail with, as return
+/// data. Basket offers need the same `remaining_accounts` as the take. Nothing is
+/// written and nobody needs to sign, so clients get the program's own numbers
+/// without replicating its pricing and rounding. A quote stays valid until the
+/// offer or the accounts it read change.
+#[derive(Accounts)]
+pub struct QuoteTake<'info> {
+    /// The person who would take the offer.
+    /// CHECK: only its key is used, to check the taker's accounts.
+    pub taker: UncheckedAccount<'info>,
+
+    /// The owner of the account that would receive the offered tokens.
+    /// CHECK: only its key is used, to check the recipient's basket accounts.
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The owner of the account that would receive the taker's payment; the quote
+    /// checks it's the offer's `payout`.
+    /// CHECK: only its key is used.
+    pub payout: UncheckedAccount<'info>,
+
+    /// Whoever paid the rent for the offer; the quote checks it's the offer's `rent_payer`.
+    /// CHECK: only its key is used.
+    pub rent_payer: UncheckedAccount<'info>,
+
+    /// The person who made the offer. The `has_one = maker` constraint on the `offer`
+    /// account ensures this is the correct maker.
+    pub maker: SystemAccount<'info>,
+
+    /// The token offered by the maker
+    #[account(mint::token_program = token_program)]
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// The token the taker would pay with: `token_mint_b` or any of the offer's
+    /// `payment_options`.
+    #[account(mint::token_program = token_program)]
+    pub token_mint_b: InterfaceAccount<'info, Mint>,
+
+    /// The token metadata account of `token_mint_b`. Only needed for collection bids.
+    pub token_b_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    /// The taker's token account for `token_mint_b`. When given, the quote also
+    /// checks the taker can afford the take.
+    #[account(
+        token::mint = token_mint_b,
+        token::authority = taker,
+        token::token_program = token_program
+    )]
+    pub taker_token_account_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The offer being quoted. Unlike `take_offer`, a mint the offer doesn't accept
+    /// is reported in the quote rather than failing the instruction.
+    #[account(
+        has_one = maker,
+        has_one = token_mint_a,
+        seeds = [b"offer", maker.key().as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The maker's token account for `token_mint_a`. Only needed for `Custody::Delegate` offers.
+    #[account(
+        token::mint = token_mint_a,
+        token::authority = maker,
+        token::token_program = token_program
+    )]
+    pub maker_token_account_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The program-owned PDA the maker approved on `maker_token_account_a`.
+    /// Only needed for `Custody::Delegate` offers.
+    /// CHECK: only its key is used, to check the approval.
+    #[account(seeds = [b"delegate", maker.key().as_ref()], bump)]
+    pub delegate: Option<UncheckedAccount<'info>>,
+
+    /// The attestation authority registry. Only needed for offers requiring an attestation.
+    #[account(seeds = [b"registry"], bump = registry.bump)]
+    pub registry: Option<Box<Account<'info, Registry>>>,
+
+    /// The taker's attestation, issued by one of the registry's authorities.
+    /// Only needed for offers requiring an attestation.
+    #[account(
+        seeds = [b"attestation", attestation.authority.as_ref(), taker.key().as_ref()],
+        bump = attestation.bump,
+    )]
+    pub attestation: Option<Box<Account<'info, Attestation>>>,
+
+    /// A token account of the taker holding the offer's gate token. Only needed for
+    /// token-gated offers.
+    #[account(token::authority = taker)]
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The Solana Token Program, used to check the mints and token accounts.
+    pub token_program: Interface<'info, TokenInterface>,
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::Arbitration};
+
+pub fn save_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
+    let arbitration = &mut ctx.accounts.arbitration;
+    let authority = ctx.accounts.authority.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    if authority != arbitration.maker && authority != arbitration.taker {
+        return Err(ErrorCode::NotArbitrationParty.into());
+    }
+
+    if arbitration.disputed {
+        return Err(ErrorCode::AlreadyDisputed.into());
+    }
+
+    if now >= arbitration.deadline {
+        return Err(ErrorCode::ArbitrationExpired.into());
+    }
+
+    // The arbiter gets a full timeout to resolve the dispute
+    arbitration.disputed = true;
+    arbitration.deadline = now + arbitration.terms.timeout_seconds;
+    Ok(())
+}
+
+/// The `RaiseDispute` struct defines the accounts required for the maker or the
+/// taker to dispute an arbitrated deal before its deadline, handing it to the arbiter.
+#[derive(Accounts)]
+pub struct RaiseDispute<'info> {
+    /// The maker or the taker of the deal.
+    pub authority: Signer<'info>,
+
+    /// The arbitration being disputed.
+    #[account(
+        mut,
+        seeds = [b"arbitration", arbitration.offer.as_ref()],
+        bump = arbitration.bump,
+    )]
+    pub arbitration: Account<'info, Arbitration>,
+}
@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::ErrorCode,
+    state::Htlc,
+    utils::{close_token_account, transfer_tokens_with_signer},
+};
+
+pub fn check_htlc_expired(ctx: &Context<RefundHtlc>) -> Result<()> {
+    if !ctx.accounts.htlc.is_expired(Clock::get()?.unix_timestamp) {
+        return Err(ErrorCode::HtlcNotExpired.into());
+    }
+    Ok(())
+}
+
+pub fn withdraw_from_vault_and_close_it(ctx: Context<RefundHtlc>) -> Result<()> {
+    let seeds = [
+        b"htlc",
+        ctx.accounts.maker.key.as_ref(),
+        &ctx.accounts.htlc.id.to_le_bytes(),
+        &[ctx.accounts.htlc.bump],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    transfer_tokens_with_signer(
+        &ctx.accounts.vault,
+        &ctx.accounts.maker_token_account,
+        &ctx.accounts.vault.amount,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.htlc.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )?;
+
+    close_token_account(
+        &ctx.accounts.vault,
+        &ctx.accounts.rent_payer.to_account_info(),
+        &ctx.accounts.htlc.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )
+}
+
+/// The `RefundHtlc` struct defines the accounts required to return the tokens of
+/// an unclaimed HTLC to the maker once its deadline has passed.
+///
+/// Anyone can submit the refund, but the tokens always go back to the `maker`.
+#[derive(Accounts)]
+pub struct RefundHtlc<'info> {
+    /// The account paying for the transaction and for `maker_token_account` if it
+    /// has to be created.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The person who locked the tokens. The `has_one = maker` constraint on the
+    /// `htlc` account ensures this is the correct maker.
+    pub maker: SystemAccount<'info>,
+
+    /// Whoever paid the rent for the `htlc` and `vault`. The rent of the closed
+    /// accounts is returned to them.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// The locked token
+    #[account(mint::token_program = token_program)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The maker's token account for `token_mint`, where the tokens go back to.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The HTLC being refunded. `close` returns its rent to the `rent_payer`.
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = maker,
+        has_one = rent_payer,
+        has_one = token_mint,
+        seeds = [b"htlc", maker.key().as_ref(), htlc.id.to_le_bytes().as_ref()],
+        bump = htlc.bump,
+    )]
+    pub htlc: Account<'info, Htlc>,
+
+    /// The vault holding the locked tokens, owned by the `htlc` PDA.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = htlc,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create `maker_token_account`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create `maker_token_account`.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::ErrorCode,
+    state::MilestoneEscrow,
+    utils::{close_token_account, transfer_tokens_with_signer},
+};
+
+pub fn mark_milestone_released(ctx: &mut Context<ReleaseMilestone>, index: u8) -> Result<u64> {
+    let now = Clock::get()?.unix_timestamp;
+    let is_maker = ctx.accounts.authority.key() == ctx.accounts.maker.key();
+
+    let milestone = ctx
+        .accounts
+        .escrow
+        .milestones
+        .get_mut(index as usize)
+        .ok_or(ErrorCode::InvalidMilestoneIndex)?;
+
+    if milestone.released {
+        return Err(ErrorCode::MilestoneAlreadyReleased.into());
+    }
+
+    // The maker can release a milestone at any time, anyone else only once it's due
+    if !is_maker && now < milestone.release_at {
+        return Err(ErrorCode::MilestoneNotDue.into());
+    }
+
+    milestone.released = true;
+    Ok(milestone.amount)
+}
+
+pub fn send_milestone_to_beneficiary(ctx: Context<ReleaseMilestone>, amount: u64) -> Result<()> {
+    let seeds = [
+        b"milestones",
+        ctx.accounts.maker.key.as_ref(),
+        &ctx.accounts.escrow.id.to_le_bytes(),
+        &[ctx.accounts.escrow.bump],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    transfer_tokens_with_signer(
+        &ctx.accounts.vault,
+        &ctx.accounts.beneficiary_token_account,
+        &amount,
+        &ctx.accounts.token_mint,
+        &ctx.accounts.escrow.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )?;
+
+    if !ctx.accounts.escrow.is_fully_released() {
+        return Ok(());
+    }
+
+    // The last milestone closes the vault and the escrow
+    close_token_account(
+        &ctx.accounts.vault,
+        &ctx.accounts.rent_payer.to_account_info(),
+        &ctx.accounts.escrow.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )?;
+
+    ctx.accounts
+        .escrow
+        .close(ctx.accounts.rent_payer.to_account_info())
+}
+
+/// The `ReleaseMilestone` struct defines the accounts required to pay one milestone
+/// of a milestone escrow to its beneficiary.
+///
+/// The maker can release a milestone at any time. Once its `release_at` has passed,
+/// anyone can, so the beneficiary doesn't depend on the maker to get paid. Releasing
+/// the last milestone closes the escrow and its vault.
+#[derive(Accounts)]
+pub struct ReleaseMilestone<'info> {
+    /// The maker, or anyone once the milestone is due.
+    pub authority: Signer<'info>,
+
+    /// The account paying for the transaction and for `beneficiary_token_account`
+    /// if it has to be created.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The person who locked the tokens. The `has_one = maker` constraint on the
+    /// `escrow` account ensures this is the correct maker.
+    pub maker: SystemAccount<'info>,
+
+    /// The person the tokens were locked for.
+    pub beneficiary: SystemAccount<'info>,
+
+    /// Whoever paid the rent for the `escrow` and `vault`. The rent of the closed
+    /// accounts is returned to them.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// The locked token
+    #[account(mint::token_program = token_program)]
+    pub token_mint: InterfaceAccount<'info, Mint>,
+
+    /// The beneficiary's token account for `token_mint`, where the tokens go.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = beneficiary,
+        associated_token::token_program = token_program,
+    )]
+    pub beneficiary_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The escrow being released. It's closed, returning its rent to the
+    /// `rent_payer`, once every milestone has been released.
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = beneficiary,
+        has_one = rent_payer,
+        has_one = token_mint,
+        seeds = [b"milestones", maker.key().as_ref(), escrow.id.to_le_bytes().as_ref()],
+        bump = escrow.bump,
+    )]
+    pub escrow: Account<'info, MilestoneEscrow>,
+
+    /// The vault holding the locked tokens, owned by the `escrow` PDA.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create `beneficiary_token_account`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create `beneficiary_token_account`.
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,247 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR,
+    error::ErrorCode,
+    state::{Custody, Offer},
+    utils::{close_token_account, transfer_tokens, transfer_tokens_with_signer},
+};
+
+pub fn check_offer_commitment(ctx: &Context<ReplaceOffer>) -> Result<()> {
+    if ctx
+        .accounts
+        .offer
+        .is_committed(Clock::get()?.unix_timestamp)
+    {
+        return Err(ErrorCode::OfferCommitted.into());
+    }
+    Ok(())
+}
+
+pub fn move_offered_tokens_to_new_vault(
+    ctx: &Context<ReplaceOffer>,
+    token_a_offered_amount: u64,
+) -> Result<()> {
+    let seeds = [
+        b"offer",
+        ctx.accounts.offer.maker.as_ref(),
+        &ctx.accounts.offer.id.to_le_bytes(),
+        &[ctx.accounts.offer.bump],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    let vault_amount = ctx.accounts.vault.amount;
+
+    // When the offered token doesn't change, tokens go straight from the old vault
+    // to the new one, and only the difference touches the maker's ATA
+    let carried_over = if ctx.accounts.token_mint_a.key() == ctx.accounts.new_token_mint_a.key() {
+        vault_amount.min(token_a_offered_amount)
+    } else {
+        0
+    };
+
+    if carried_over > 0 {
+        transfer_tokens_with_signer(
+            &ctx.accounts.vault,
+            &ctx.accounts.new_vault,
+            &carried_over,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.offer.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    // Whatever is left in the old vault goes back to the maker
+    let leftover = vault_amount - carried_over;
+    if leftover > 0 {
+        transfer_tokens_with_signer(
+            &ctx.accounts.vault,
+            &ctx.accounts.maker_token_account_a,
+            &leftover,
+            &ctx.accounts.token_mint_a,
+            &ctx.accounts.offer.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    // Top up the new vault from the maker if the new offer is bigger
+    let top_up = token_a_offered_amount - carried_over;
+    if top_up > 0 {
+        transfer_tokens(
+            &ctx.accounts.maker_new_token_account_a,
+            &ctx.accounts.new_vault,
+            &top_up,
+            &ctx.accounts.new_token_mint_a,
+            &ctx.accounts.maker,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    // Old vault is empty and can be closed safely now
+    close_token_account(
+        &ctx.accounts.vault,
+        &ctx.accounts.rent_payer.to_account_info(),
+        &ctx.accounts.offer.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )
+}
+
+pub fn save_new_offer(
+    ctx: Context<ReplaceOffer>,
+    new_offer_id: u64,
+    token_a_offered_amount: u64,
+    token_b_amount_wanted: u64,
+) -> Result<()> {
+    ctx.accounts.new_offer.set_inner(Offer {
+        token_mint_a: ctx.accounts.new_token_mint_a.key(),
+        token_mint_b: ctx.accounts.new_token_mint_b.key(),
+        token_b_amount_wanted,
+        // The manager keeps managing the re-posted offer
+        manager: ctx.accounts.offer.manager,
+        manager_expires_at: ctx.accounts.offer.manager_expires_at,
+        token_a_offered_amount,
+        // Proceeds keep going to the same wallet
+        payout: ctx.accounts.offer.payout,
+        hashlock: ctx.accounts.offer.hashlock,
+        min_attestation_tier: ctx.accounts.offer.min_attestation_tier,
+        token_gate: ctx.accounts.offer.token_gate,
+        vesting: ctx.accounts.offer.vesting,
+        arbitration: ctx.accounts.offer.arbitration,
+        // The commitment was on the old terms, and it's over by now
+        min_live_until: None,
+        fill_policy: ctx.accounts.offer.fill_policy,
+        ..Offer::new(
+            new_offer_id,
+            *ctx.accounts.maker.key,
+            *ctx.accounts.payer.key,
+            ctx.bumps.new_offer,
+        )
+    });
+    Ok(())
+}
+
+/// The `ReplaceOffer` struct defines the accounts required to atomically swap an
+/// existing offer for a new one.
+///
+/// The old `offer` and its `vault` are closed, and a new offer PDA (with a new id)
+/// and vault are created in the same instruction, so the maker never has a window
+/// without a live offer. A new id is required so that a taker who built a
+/// transaction against the old terms can't end up filling the new ones.
+#[derive(Accounts)]
+#[instruction(new_offer_id: u64)]
+pub struct ReplaceOffer<'info> {
+    /// The current owner of the offer, who makes the new one: the maker, unless the
+    /// offer was transferred. They must sign the transaction.
+    pub maker: Signer<'info>,
+
+    /// The account paying for the new accounts. It can be the `maker` itself or a
+    /// relayer sponsoring the transaction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Whoever paid the rent for the old `offer` and `vault`. The rent of the closed
+    /// accounts is returned to them.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// The token the maker was offering in the old offer
+    #[account(mint::token_program = token_program)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The maker's token account for `token_mint_a`. Any tokens from the old vault
+    /// that are not carried over to the new vault are returned here.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The offer being replaced. It is closed at the end of the instruction,
+    /// returning its rent to the `rent_payer`.
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = rent_payer,
+        has_one = token_mint_a,
+        constraint = offer.current_owner() == maker.key() @ ErrorCode::NotOfferOwner,
+        constraint = offer.custody == Custody::Vault @ ErrorCode::OfferCustodyMismatch,
+        constraint = !offer.is_basket() @ ErrorCode::BasketNotSupported,
+        constraint = offer.token_a_collection.is_none() && offer.token_b_collection.is_none()
+            @ ErrorCode::CollectionNotSupported,
+        constraint = offer.payment_options.is_empty() @ ErrorCode::OtherPaymentMintsNotSupported,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Box<Account<'info, Offer>>,
+
+    /// The vault of the old offer. It is emptied and closed.
+    #[account(
+        mut,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = offer,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The token the maker is offering in the new offer. May be the same as `token_mint_a`.
+    #[account(mint::token_program = token_program)]
+    pub new_token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token the maker wants in return in the new offer.
+    #[account(mint::token_program = token_program)]
+    pub new_token_mint_b: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The maker's token account for `new_token_mint_a`, used to top up the new vault
+    /// when the new offer is bigger than what the old vault held. This is the same
+    /// account as `maker_token_account_a` when the offered token doesn't change.
+    #[account(
+        mut,
+        associated_token::mint = new_token_mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_new_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The new offer account, created the same way as in `make_offer`.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Offer::INIT_SPACE,
+        seeds = [b"offer", maker.key().as_ref(), new_offer_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub new_offer: Box<Account<'info, Offer>>,
+
+    /// The vault of the new offer, owned by the `new_offer` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = new_token_mint_a,
+        associated_token::authority = new_offer,
+        associated_token::token_program = token_program
+    )]
+    pub new_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create the new vault
+    /// and the `maker_token_account_a` if it doesn't exist.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create new accounts (like
+    /// the `new_offer` and `new_vault` accounts).
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::state::Attestation;
+
+/// The `RevokeAttestation` struct defines the accounts required for an attestation
+/// authority to withdraw an attestation it issued.
+///
+/// The `attestation` account is closed, returning its rent to whoever paid for it.
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    /// The authority that issued the attestation.
+    pub authority: Signer<'info>,
+
+    /// Whoever paid the rent for the `attestation` when it was issued.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// The attestation being revoked.
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = authority,
+        has_one = rent_payer,
+        seeds = [b"attestation", authority.key().as_ref(), attestation.wallet.as_ref()],
+        bump = attestation.bump,
+    )]
+    pub attestation: Account<'info, Attestation>,
+}
@@ -0,0 +1,58 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use crate::{
+    constants::MAX_BPS,
+    error::ErrorCode,
+    state::{ArbitrationTerms, Offer},
+    utils::check_offer_made_in_this_transaction,
+};
+
+pub fn save_offer_arbitration(
+    ctx: Context<SetOfferArbitration>,
+    arbitration: Option<ArbitrationTerms>,
+) -> Result<()> {
+    check_offer_made_in_this_transaction(
+        &ctx.accounts.offer.key(),
+        &ctx.accounts.instructions.to_account_info(),
+    )?;
+
+    if let Some(terms) = arbitration {
+        if terms.fee_bps > MAX_BPS
+            || terms.timeout_seconds <= 0
+            || terms.arbiter == ctx.accounts.owner.key()
+        {
+            return Err(ErrorCode::InvalidArbitrationTerms.into());
+        }
+    }
+
+    ctx.accounts.offer.arbitration = arbitration;
+    Ok(())
+}
+
+/// The `SetOfferArbitration` struct defines the accounts required to make (or stop
+/// making) an offer arbitrated, e.g. for service deals.
+///
+/// The taker's payment is then held after the take instead of going to the maker,
+/// until the parties release it or the arbiter resolves a dispute. It only works in
+/// the transaction making the offer, after the instruction that makes it, so the
+/// terms can't change once anyone can take the offer.
+#[derive(Accounts)]
+pub struct SetOfferArbitration<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred.
+    pub owner: Signer<'info>,
+
+    /// The offer being updated. The owner and `seeds` constraints are used to
+    /// securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The instructions sysvar, used to find the instruction making the `offer`.
+    /// CHECK: the address is checked against the sysvar id.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
@@ -0,0 +1,43 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use crate::{error::ErrorCode, state::Offer, utils::check_offer_made_in_this_transaction};
+
+pub fn save_offer_attestation(
+    ctx: Context<SetOfferAttestation>,
+    min_attestation_tier: Option<u8>,
+) -> Result<()> {
+    check_offer_made_in_this_transaction(
+        &ctx.accounts.offer.key(),
+        &ctx.accounts.instructions.to_account_info(),
+    )?;
+
+    ctx.accounts.offer.min_attestation_tier = min_attestation_tier;
+    Ok(())
+}
+
+/// The `SetOfferAttestation` struct defines the accounts required to require (or
+/// stop requiring) takers to hold a valid attestation of at least a given tier.
+///
+/// It only works in the transaction making the offer, after the instruction that
+/// makes it, so the requirement is in place before anyone can take the offer and a
+/// taker never finds it changed.
+#[derive(Accounts)]
+pub struct SetOfferAttestation<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred.
+    pub owner: Signer<'info>,
+
+    /// The offer being updated. The owner and `seeds` constraints are used to
+    /// securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The instructions sysvar, used to find the instruction making the `offer`.
+    /// CHECK: the address is checked against the sysvar id.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
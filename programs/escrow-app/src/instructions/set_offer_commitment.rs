@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, events::OfferCommitted, state::Offer};
+
+pub fn save_offer_commitment(ctx: Context<SetOfferCommitment>, min_live_until: i64) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+
+    // A commitment can only be extended, otherwise it wouldn't mean anything
+    if offer
+        .min_live_until
+        .is_some_and(|current| min_live_until < current)
+    {
+        return Err(ErrorCode::CommitmentShortened.into());
+    }
+
+    offer.min_live_until = Some(min_live_until);
+
+    emit!(OfferCommitted {
+        offer: offer.key(),
+        maker: offer.maker,
+        min_live_until,
+    });
+    Ok(())
+}
+
+/// The `SetOfferCommitment` struct defines the accounts required for the maker to
+/// commit to keeping an offer live: until `min_live_until`, it can be neither
+/// cancelled nor replaced, only taken.
+///
+/// It's a signal of a firm quote to takers preparing a fill. The commitment can be
+/// extended later, but never shortened or removed.
+#[derive(Accounts)]
+pub struct SetOfferCommitment<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred.
+    pub owner: Signer<'info>,
+
+    /// The offer being updated. The owner and `seeds` constraints are used to
+    /// securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+}
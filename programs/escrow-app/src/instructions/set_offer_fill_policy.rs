@@ -0,0 +1,67 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    error::ErrorCode,
+    state::{FillPolicy, Offer},
+    utils::{check_nft_mint, check_offer_made_in_this_transaction},
+};
+
+pub fn save_offer_fill_policy(
+    ctx: Context<SetOfferFillPolicy>,
+    fill_policy: FillPolicy,
+) -> Result<()> {
+    check_offer_made_in_this_transaction(
+        &ctx.accounts.offer.key(),
+        &ctx.accounts.instructions.to_account_info(),
+    )?;
+
+    let offer = &ctx.accounts.offer;
+    if let FillPolicy::Partial { min_fill_amount } = fill_policy {
+        // A single NFT can't be split, and a collection bid is paid with one
+        let is_nft_offer = check_nft_mint(&ctx.accounts.token_mint_a).is_ok();
+        if is_nft_offer || offer.token_b_collection.is_some() {
+            return Err(ErrorCode::PartialFillNotSupported.into());
+        }
+
+        if min_fill_amount == 0 || min_fill_amount > offer.token_a_offered_amount {
+            return Err(ErrorCode::InvalidFillPolicy.into());
+        }
+    }
+
+    ctx.accounts.offer.fill_policy = fill_policy;
+    Ok(())
+}
+
+/// The `SetOfferFillPolicy` struct defines the accounts required to choose whether an
+/// offer is all-or-nothing or can be taken in parts, and the minimum size of a part.
+///
+/// Offers are all-or-nothing by default. With partial fills, a take can't leave less
+/// than the minimum behind either, so the maker isn't left with a dust remainder.
+/// It only works in the transaction making the offer, after the instruction that
+/// makes it, so the policy can't change once anyone can take the offer.
+#[derive(Accounts)]
+pub struct SetOfferFillPolicy<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred.
+    pub owner: Signer<'info>,
+
+    /// The offer being updated. The owner and `seeds` constraints are used to
+    /// securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        has_one = token_mint_a,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The token the offer sells, to tell NFT offers apart
+    pub token_mint_a: InterfaceAccount<'info, Mint>,
+
+    /// The instructions sysvar, used to find the instruction making the `offer`.
+    /// CHECK: the address is checked against the sysvar id.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
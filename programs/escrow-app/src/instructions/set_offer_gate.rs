@@ -0,0 +1,44 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use crate::{
+    error::ErrorCode,
+    state::{Offer, TokenGate},
+    utils::check_offer_made_in_this_transaction,
+};
+
+pub fn save_offer_gate(ctx: Context<SetOfferGate>, token_gate: Option<TokenGate>) -> Result<()> {
+    check_offer_made_in_this_transaction(
+        &ctx.accounts.offer.key(),
+        &ctx.accounts.instructions.to_account_info(),
+    )?;
+
+    ctx.accounts.offer.token_gate = token_gate;
+    Ok(())
+}
+
+/// The `SetOfferGate` struct defines the accounts required to restrict (or stop
+/// restricting) an offer to takers holding a given token, like a membership token.
+///
+/// It only works in the transaction making the offer, after the instruction that
+/// makes it, so the gate is in place before anyone can take the offer and can't be
+/// moved afterwards.
+#[derive(Accounts)]
+pub struct SetOfferGate<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred.
+    pub owner: Signer<'info>,
+
+    /// The offer being updated. The owner and `seeds` constraints are used to
+    /// securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The instructions sysvar, used to find the instruction making the `offer`.
+    /// CHECK: the address is checked against the sysvar id.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
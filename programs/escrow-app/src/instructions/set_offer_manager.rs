@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{error::ErrorCode, state::Offer};
+
+pub fn save_offer_manager(
+    ctx: Context<SetOfferManager>,
+    manager: Option<Pubkey>,
+    manager_expires_at: i64,
+) -> Result<()> {
+    if manager.is_some() && manager_expires_at <= Clock::get()?.unix_timestamp {
+        return Err(ErrorCode::InvalidManagerExpiry.into());
+    }
+
+    let offer = &mut ctx.accounts.offer;
+    offer.manager = manager;
+    offer.manager_expires_at = if manager.is_some() {
+        manager_expires_at
+    } else {
+        0
+    };
+    Ok(())
+}
+
+/// The `SetOfferManager` struct defines the accounts required to set (or revoke)
+/// the manager of an offer.
+///
+/// A manager is a delegate key, such as a bot's session key, that can cancel the
+/// offer without holding the owner's main key. Funds always go back to the owner.
+#[derive(Accounts)]
+pub struct SetOfferManager<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred. Only
+    /// they can change its manager.
+    pub owner: Signer<'info>,
+
+    /// The offer whose manager is being set. The owner and `seeds` constraints
+    /// are used to securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+}
@@ -0,0 +1,56 @@
+use anchor_lang::{prelude::*, solana_program::sysvar};
+
+use crate::{
+    error::ErrorCode,
+    state::{Offer, VestingSchedule},
+    utils::check_offer_made_in_this_transaction,
+};
+
+pub fn save_offer_vesting(
+    ctx: Context<SetOfferVesting>,
+    vesting: Option<VestingSchedule>,
+) -> Result<()> {
+    check_offer_made_in_this_transaction(
+        &ctx.accounts.offer.key(),
+        &ctx.accounts.instructions.to_account_info(),
+    )?;
+
+    if let Some(schedule) = vesting {
+        if schedule.duration_seconds <= 0
+            || schedule.cliff_seconds < 0
+            || schedule.cliff_seconds > schedule.duration_seconds
+        {
+            return Err(ErrorCode::InvalidVestingSchedule.into());
+        }
+    }
+
+    ctx.accounts.offer.vesting = vesting;
+    Ok(())
+}
+
+/// The `SetOfferVesting` struct defines the accounts required to make (or stop
+/// making) the offered tokens vest for the taker instead of being sent right away.
+///
+/// It only works in the transaction making the offer, after the instruction that
+/// makes it, so the schedule is in place before anyone can take the offer and a
+/// taker never finds it changed, even on an offer the maker committed to.
+#[derive(Accounts)]
+pub struct SetOfferVesting<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred.
+    pub owner: Signer<'info>,
+
+    /// The offer being updated. The owner and `seeds` constraints are used to
+    /// securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// The instructions sysvar, used to find the instruction making the `offer`.
+    /// CHECK: the address is checked against the sysvar id.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
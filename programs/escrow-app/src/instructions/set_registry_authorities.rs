@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::MAX_ATTESTATION_AUTHORITIES, error::ErrorCode, state::Registry};
+
+pub fn save_registry_authorities(
+    ctx: Context<SetRegistryAuthorities>,
+    authorities: Vec<Pubkey>,
+) -> Result<()> {
+    if authorities.len() > MAX_ATTESTATION_AUTHORITIES {
+        return Err(ErrorCode::TooManyAttestationAuthorities.into());
+    }
+
+    ctx.accounts.registry.authorities = authorities;
+    Ok(())
+}
+
+/// The `SetRegistryAuthorities` struct defines the accounts required to replace the
+/// list of attestation authorities.
+///
+/// Removing an authority invalidates every attestation it issued.
+#[derive(Accounts)]
+pub struct SetRegistryAuthorities<'info> {
+    /// The registry admin. Only they can change the authorities.
+    pub admin: Signer<'info>,
+
+    /// The registry being updated.
+    #[account(
+        mut,
+        has_one = admin @ ErrorCode::NotProgramAdmin,
+        seeds = [b"registry"],
+        bump = registry.bump,
+    )]
+    pub registry: Account<'info, Registry>,
+}
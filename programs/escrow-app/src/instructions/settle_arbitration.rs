@@ -0,0 +1,300 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    constants::MAX_BPS,
+    error::ErrorCode,
+    state::{Arbitration, DisputeOutcome},
+    utils::{close_token_account, transfer_tokens_with_signer},
+};
+
+/// Either party can release the payment to the other one: the taker to the maker
+/// once satisfied, or the maker back to the taker.
+pub fn check_release(ctx: &Context<SettleArbitration>, to_maker: bool) -> Result<(u16, bool)> {
+    let arbitration = &ctx.accounts.arbitration;
+    let giving_up = if to_maker {
+        arbitration.taker
+    } else {
+        arbitration.maker
+    };
+
+    if ctx.accounts.authority.key() != giving_up {
+        return Err(ErrorCode::NotArbitrationParty.into());
+    }
+
+    let maker_share_bps = if to_maker { MAX_BPS } else { 0 };
+    Ok((maker_share_bps, false))
+}
+
+pub fn check_resolution(
+    ctx: &Context<SettleArbitration>,
+    maker_share_bps: u16,
+) -> Result<(u16, bool)> {
+    let arbitration = &ctx.accounts.arbitration;
+
+    if ctx.accounts.authority.key() != arbitration.terms.arbiter {
+        return Err(ErrorCode::NotArbiter.into());
+    }
+
+    if !arbitration.disputed {
+        return Err(ErrorCode::ArbitrationNotDisputed.into());
+    }
+
+    // Past the deadline, only the default outcome applies
+    if Clock::get()?.unix_timestamp >= arbitration.deadline {
+        return Err(ErrorCode::ArbitrationExpired.into());
+    }
+
+    if maker_share_bps > MAX_BPS {
+        return Err(ErrorCode::InvalidArbitrationShare.into());
+    }
+
+    Ok((maker_share_bps, true))
+}
+
+pub fn check_expired(ctx: &Context<SettleArbitration>) -> Result<(u16, bool)> {
+    let arbitration = &ctx.accounts.arbitration;
+
+    if Clock::get()?.unix_timestamp < arbitration.deadline {
+        return Err(ErrorCode::ArbitrationNotExpired.into());
+    }
+
+    let maker_share_bps = match arbitration.terms.default_outcome {
+        DisputeOutcome::Release => MAX_BPS,
+        DisputeOutcome::Refund => 0,
+    };
+    Ok((maker_share_bps, false))
+}
+
+pub fn pay_out_and_close_vaults(
+    ctx: Context<SettleArbitration>,
+    maker_share_bps: u16,
+    with_fee: bool,
+) -> Result<()> {
+    let arbitration = &ctx.accounts.arbitration;
+    let (maker_amount, taker_amount, fee) = arbitration
+        .split(maker_share_bps, with_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let (recipient_amount, maker_amount_a) = arbitration
+        .split_token_a(maker_share_bps)
+        .ok_or(ErrorCode::MathOverflow)?;
+
+    let seeds = [
+        b"arbitration",
+        arbitration.offer.as_ref(),
+        &[arbitration.bump],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    for (to, amount) in [
+        (Some(&ctx.accounts.payout_token_account), maker_amount),
+        (Some(&ctx.accounts.taker_token_account), taker_amount),
+        (ctx.accounts.arbiter_token_account.as_ref(), fee),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        let to = to.ok_or(ErrorCode::MissingArbiterTokenAccount)?;
+        transfer_tokens_with_signer(
+            &ctx.accounts.arbitration_vault,
+            to,
+            &amount,
+            &ctx.accounts.token_mint,
+            &arbitration.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    // Token A goes to the recipient for the part of the deal that went through, and
+    // back to the maker for the rest
+    for (to, amount) in [
+        (&ctx.accounts.recipient_token_account_a, recipient_amount),
+        (&ctx.accounts.maker_token_account_a, maker_amount_a),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        transfer_tokens_with_signer(
+            &ctx.accounts.arbitration_vault_a,
+            to,
+            &amount,
+            &ctx.accounts.token_mint_a,
+            &arbitration.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    for vault in [
+        &ctx.accounts.arbitration_vault,
+        &ctx.accounts.arbitration_vault_a,
+    ] {
+        close_token_account(
+            vault,
+            &ctx.accounts.rent_payer.to_account_info(),
+            &arbitration.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The `SettleArbitration` struct defines the accounts required to pay out the held
+/// payment of an arbitrated deal, which `release_arbitration`, `resolve_dispute` and
+/// `settle_expired_arbitration` share.
+///
+/// The payment is split between the maker's payout wallet, the taker and, for
+/// resolved disputes, the arbiter's fee. Token A is split the same way between the
+/// recipient and the maker, who gets back what the taker doesn't get. The
+/// arbitration and its vaults are then closed, returning their rent to whoever paid
+/// for them.
+#[derive(Accounts)]
+pub struct SettleArbitration<'info> {
+    /// The maker or taker releasing the payment, the arbiter resolving a dispute,
+    /// or anyone once the deadline has passed.
+    pub authority: Signer<'info>,
+
+    /// The account paying for any token account that has to be created.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The taker of the deal. The `has_one = taker` constraint on the `arbitration`
+    /// account ensures this is the correct taker.
+    pub taker: SystemAccount<'info>,
+
+    /// The owner of the offer when it was taken, getting back the token A the
+    /// recipient doesn't get. The `has_one = maker` constraint on the `arbitration`
+    /// account ensures this is the correct one.
+    pub maker: SystemAccount<'info>,
+
+    /// The owner of the account receiving the maker's share.
+    /// CHECK: only used as the authority of `payout_token_account`; the
+    /// `has_one = payout` constraint on `arbitration` ensures it's the right one.
+    pub payout: UncheckedAccount<'info>,
+
+    /// The owner of the account receiving the taker's token A.
+    /// CHECK: only used as the authority of `recipient_token_account_a`; the
+    /// `has_one = recipient` constraint on `arbitration` ensures it's the right one.
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The arbiter named in the offer's terms.
+    /// CHECK: only used as the authority of `arbiter_token_account`.
+    #[account(address = arbitration.terms.arbiter)]
+    pub arbiter: UncheckedAccount<'info>,
+
+    /// Whoever paid the rent for the `arbitration` and its vaults when the offer was taken.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
+    /// The token the taker paid with
+    #[account(mint::token_program = token_program)]
+    pub token_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The token the maker offered
+    #[account(mint::token_program = token_program)]
+    pub token_mint_a: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The payout wallet's token account for `token_mint`, receiving the maker's share.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = payout,
+        associated_token::token_program = token_program,
+    )]
+    pub payout_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The taker's token account for `token_mint`, receiving the taker's share.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The arbiter's token account for `token_mint`, receiving the fee. Only needed
+    /// when the arbiter resolves a dispute with a non-zero fee.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint,
+        associated_token::authority = arbiter,
+        associated_token::token_program = token_program,
+    )]
+    pub arbiter_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The recipient's token account for `token_mint_a`, receiving their share.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub recipient_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The maker's token account for `token_mint_a`, getting back the rest.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_token_account_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The arbitration left behind by `take_offer`. `close` returns its rent to the `rent_payer`.
+    #[account(
+        mut,
+        close = rent_payer,
+        has_one = maker,
+        has_one = taker,
+        has_one = payout,
+        has_one = recipient,
+        has_one = rent_payer,
+        has_one = token_mint,
+        has_one = token_mint_a,
+        seeds = [b"arbitration", arbitration.offer.as_ref()],
+        bump = arbitration.bump,
+    )]
+    pub arbitration: Box<Account<'info, Arbitration>>,
+
+    /// The vault holding the payment, owned by the `arbitration` PDA.
+    #[account(
+        mut,
+        associated_token::mint = token_mint,
+        associated_token::authority = arbitration,
+        associated_token::token_program = token_program
+    )]
+    pub arbitration_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The vault holding token A, owned by the `arbitration` PDA.
+    #[account(
+        mut,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = arbitration,
+        associated_token::token_program = token_program
+    )]
+    pub arbitration_vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The Solana Token Program. This is required for all token-related operations,
+    /// such as transferring tokens.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create the token accounts.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create the token accounts.
+    pub system_program: Program<'info, System>,
+}
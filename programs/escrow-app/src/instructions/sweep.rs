@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{
+    error::ErrorCode,
+    state::{Custody, Offer},
+    utils::{close_token_account, transfer_tokens_with_signer},
+};
+
+/// How much of `token_account` the offer still owes its taker, if it's one of the
+/// offer's own vaults: what's left of token A in the vault, possibly nothing (e.g.
+/// a basket-only offer), or the amount of a basket leg in the leg's vault. `None`
+/// for any other token account of the offer PDA, whose tokens were all sent there
+/// by mistake.
+fn amount_owed(
+    offer: &Account<Offer>,
+    token_account: &Pubkey,
+    token_program: &Pubkey,
+) -> Option<u64> {
+    let vault_of = |mint: &Pubkey| {
+        get_associated_token_address_with_program_id(&offer.key(), mint, token_program)
+    };
+
+    if offer.custody == Custody::Vault && *token_account == vault_of(&offer.token_mint_a) {
+        return Some(offer.token_a_remaining_amount());
+    }
+    offer
+        .basket_offered
+        .iter()
+        .find(|leg| *token_account == vault_of(&leg.mint))
+        .map(|leg| leg.amount)
+}
+
+pub fn sweep_token_account(ctx: Context<Sweep>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+    let token_account = &ctx.accounts.token_account;
+
+    let owed = amount_owed(
+        offer,
+        &token_account.key(),
+        &ctx.accounts.token_program.key(),
+    );
+    let stray_amount = token_account.amount.saturating_sub(owed.unwrap_or(0));
+
+    // A vault without surplus has nothing to sweep, but an empty stray account can
+    // still be closed for its rent
+    if stray_amount == 0 && owed.is_some() {
+        return Err(ErrorCode::NothingToSweep.into());
+    }
+
+    let seeds = [
+        b"offer",
+        ctx.accounts.offer.maker.as_ref(),
+        &offer.id.to_le_bytes(),
+        &[offer.bump],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    if stray_amount > 0 {
+        transfer_tokens_with_signer(
+            token_account,
+            &ctx.accounts.owner_token_account,
+            &stray_amount,
+            &ctx.accounts.mint,
+            &offer.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    // The offer's vaults stay open for the take, the rest is closed
+    if owed.is_none() {
+        close_token_account(
+            token_account,
+            &ctx.accounts.owner.to_account_info(),
+            &offer.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The `Sweep` struct defines the accounts required for the owner of an offer to
+/// recover tokens sent to its PDA by mistake.
+///
+/// Only the program can sign for the `offer`, so tokens sent to any of its token
+/// accounts would otherwise be stuck. Sweeping sends them to the owner and closes
+/// the account, returning its rent to the owner too. The offer's own vaults are
+/// accounted for: only the surplus over what the offer still owes its taker is
+/// swept, and they are never closed, even once they owe nothing.
+#[derive(Accounts)]
+pub struct Sweep<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred. They
+    /// must be a `Signer` to authorize the sweep.
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    /// The account paying for any new accounts created. It can be the `owner`
+    /// itself or a relayer sponsoring the transaction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The mint of the tokens being swept
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// The offer whose PDA holds the tokens. The owner and `seeds` constraints are
+    /// used to securely verify that this is the correct and valid offer PDA.
+    #[account(
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+
+    /// Any token account of the `offer` PDA for `mint`, associated or not.
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = offer,
+        token::token_program = token_program
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The owner's Associated Token Account (ATA) for `mint`, receiving the swept
+    /// tokens. `init_if_needed` means Anchor will create this account if it doesn't
+    /// already exist. The `payer` pays for the rent.
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    /// The Solana Token Program, used to transfer the tokens and close the account.
+    pub token_program: Interface<'info, TokenInterface>,
+
+    /// The Solana Associated Token Program. This is needed to create the
+    /// `owner_token_account`.
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    /// The Solana System Program. This is required to create the `owner_token_account`.
+    pub system_program: Program<'info, System>,
+}
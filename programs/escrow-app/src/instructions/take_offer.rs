@@ -1,31 +1,575 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{prelude::*, solana_program::hash::hash};
 
 use anchor_spl::{
-    associated_token::AssociatedToken,
+    associated_token::{get_associated_token_address_with_program_id, AssociatedToken},
+    metadata::MetadataAccount,
     token_interface::{
         close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
         TransferChecked,
     },
 };
 
-use crate::{error::ErrorCode, state::Offer, utils::transfer_tokens};
+use crate::{
+    constants::ANCHOR_DISCRIMINATOR,
+    error::ErrorCode,
+    events::OfferTaken,
+    state::{
+        Arbitration, Attestation, BasketLeg, Claim, Custody, FillPolicy, Offer, Registry, Vesting,
+    },
+    utils::{
+        check_delegate_allowance, check_nft_mint, check_verified_collection, close_token_account,
+        either_token_account, load_basket_leg, transfer_tokens, transfer_tokens_with_signer,
+    },
+};
+
+/// The accounts a take is checked against. `take_offer` and `quote_take` each build
+/// one from their own accounts and run `validate_take` on it, so a quote fails
+/// exactly when the take would.
+pub struct TakeAccounts<'a, 'info> {
+    pub offer: &'a Account<'info, Offer>,
+    pub taker: Pubkey,
+    pub recipient: Pubkey,
+    pub payout: Pubkey,
+    pub rent_payer: Pubkey,
+    pub token_mint_b: &'a InterfaceAccount<'info, Mint>,
+    pub token_b_metadata: Option<&'a Account<'info, MetadataAccount>>,
+    /// Always there for a take; a quote only checks the taker's balance when given
+    pub taker_token_account_b: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub maker_token_account_a: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub delegate: Option<Pubkey>,
+    pub registry: Option<&'a Account<'info, Registry>>,
+    pub attestation: Option<&'a Account<'info, Attestation>>,
+    pub gate_token_account: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Pubkey,
+}
+
+/// Runs every check of a take before anything moves, and returns what it exchanges.
+pub fn check_take<'info>(
+    ctx: &Context<'_, '_, 'info, 'info, TakeOffer<'info>>,
+    preimage: Option<[u8; 32]>,
+    token_a_amount: Option<u64>,
+) -> Result<Fill> {
+    let accounts = &ctx.accounts;
+
+    let take_accounts = TakeAccounts {
+        offer: &accounts.offer,
+        taker: accounts.taker.key(),
+        recipient: accounts.recipient.key(),
+        payout: accounts.payout.key(),
+        rent_payer: accounts.rent_payer.key(),
+        token_mint_b: &accounts.token_mint_b,
+        token_b_metadata: accounts.token_b_metadata.as_deref(),
+        taker_token_account_b: Some(&accounts.taker_token_account_b),
+        maker_token_account_a: accounts.maker_token_account_a.as_ref(),
+        delegate: accounts.delegate.as_ref().map(|delegate| delegate.key()),
+        registry: accounts.registry.as_deref(),
+        attestation: accounts.attestation.as_deref(),
+        gate_token_account: accounts.gate_token_account.as_deref(),
+        token_program: accounts.token_program.key(),
+    };
+
+    validate_take(
+        &take_accounts,
+        ctx.remaining_accounts,
+        preimage,
+        token_a_amount,
+    )
+}
+
+/// The checks shared by `take_offer` and `quote_take`. `basket_accounts` are the
+/// `remaining_accounts` settling the basket legs, as described in `settle_basket`.
+pub fn validate_take<'info>(
+    accounts: &TakeAccounts<'_, 'info>,
+    basket_accounts: &'info [AccountInfo<'info>],
+    preimage: Option<[u8; 32]>,
+    token_a_amount: Option<u64>,
+) -> Result<Fill> {
+    let offer = accounts.offer;
+
+    if accounts.payout != offer.payout || accounts.rent_payer != offer.rent_payer {
+        return Err(anchor_lang::error::ErrorCode::ConstraintHasOne.into());
+    }
+
+    check_hashlock(offer, preimage)?;
+    check_attestation(
+        offer,
+        &accounts.taker,
+        accounts.registry,
+        accounts.attestation,
+    )?;
+    check_token_gate(offer, accounts.gate_token_account)?;
+    check_payment_mint(
+        offer,
+        &accounts.taker,
+        accounts.token_mint_b,
+        accounts.token_b_metadata,
+    )?;
+
+    // An arbiter taking the offer would settle its own dispute
+    if offer
+        .arbitration
+        .is_some_and(|terms| terms.arbiter == accounts.taker)
+    {
+        return Err(ErrorCode::TakerIsArbiter.into());
+    }
+
+    let mut fill = check_fill(offer, &accounts.token_mint_b.key(), token_a_amount)?;
+
+    // What the token program would reject during the take, with a clearer error
+    if let Some(taker_token_account_b) = accounts.taker_token_account_b {
+        if taker_token_account_b.amount < fill.token_b_amount {
+            return Err(ErrorCode::InsufficientTakerBalance.into());
+        }
+    }
+
+    if offer.custody == Custody::Delegate {
+        let (Some(maker_token_account_a), Some(delegate)) =
+            (accounts.maker_token_account_a, accounts.delegate)
+        else {
+            return Err(ErrorCode::MissingCustodyAccount.into());
+        };
+
+        check_delegate_allowance(maker_token_account_a, &delegate, fill.token_a_amount)?;
+    }
+
+    fill.basket_wanted = offer.basket_wanted.clone();
+    fill.basket_offered = check_basket(accounts, basket_accounts)?;
+    Ok(fill)
+}
+
+/// Checks the accounts settling the basket legs and that the taker holds every wanted
+/// leg. Returns what each offered leg would send the recipient: its whole vault.
+fn check_basket<'info>(
+    accounts: &TakeAccounts<'_, 'info>,
+    basket_accounts: &'info [AccountInfo<'info>],
+) -> Result<Vec<BasketLeg>> {
+    let offer = accounts.offer;
+
+    if basket_accounts.len() != offer.basket_accounts_len() {
+        return Err(ErrorCode::InvalidBasketAccounts.into());
+    }
+
+    let (wanted_accounts, offered_accounts) =
+        basket_accounts.split_at(3 * offer.basket_wanted.len());
+
+    for (leg, accounts_of_leg) in offer.basket_wanted.iter().zip(wanted_accounts.chunks(3)) {
+        let leg_accounts = load_basket_leg(accounts_of_leg, leg)?;
+
+        if leg_accounts.from.owner != accounts.taker || leg_accounts.to.owner != accounts.payout {
+            return Err(ErrorCode::InvalidBasketAccounts.into());
+        }
+
+        if leg_accounts.from.amount < leg.amount {
+            return Err(ErrorCode::InsufficientTakerBalance.into());
+        }
+    }
+
+    let mut basket_offered = Vec::with_capacity(offer.basket_offered.len());
+
+    for (leg, accounts_of_leg) in offer.basket_offered.iter().zip(offered_accounts.chunks(3)) {
+        let leg_accounts = load_basket_leg(accounts_of_leg, leg)?;
+
+        let leg_vault = get_associated_token_address_with_program_id(
+            &offer.key(),
+            &leg.mint,
+            &accounts.token_program,
+        );
+        if leg_accounts.from.key() != leg_vault || leg_accounts.to.owner != accounts.recipient {
+            return Err(ErrorCode::InvalidBasketAccounts.into());
+        }
+
+        basket_offered.push(BasketLeg {
+            mint: leg.mint,
+            amount: leg_accounts.from.amount,
+        });
+    }
+
+    Ok(basket_offered)
+}
+
+pub fn check_hashlock(offer: &Offer, preimage: Option<[u8; 32]>) -> Result<()> {
+    let Some(hashlock) = offer.hashlock else {
+        return Ok(());
+    };
+
+    match preimage {
+        Some(preimage) if hash(&preimage).to_bytes() == hashlock => Ok(()),
+        _ => Err(ErrorCode::InvalidPreimage.into()),
+    }
+}
+
+pub fn check_attestation(
+    offer: &Offer,
+    taker: &Pubkey,
+    registry: Option<&Account<Registry>>,
+    attestation: Option<&Account<Attestation>>,
+) -> Result<()> {
+    let Some(min_tier) = offer.min_attestation_tier else {
+        return Ok(());
+    };
+
+    let (Some(registry), Some(attestation)) = (registry, attestation) else {
+        return Err(ErrorCode::MissingAttestation.into());
+    };
 
-pub fn send_tokens_from_taker_to_maker(ctx: &Context<TakeOffer>) -> Result<()> {
-    if ctx.accounts.taker.key() == ctx.accounts.maker.key() {
+    if attestation.wallet != *taker || !attestation.is_valid(registry, Clock::get()?.unix_timestamp)
+    {
+        return Err(ErrorCode::InvalidAttestation.into());
+    }
+
+    if attestation.tier < min_tier {
+        return Err(ErrorCode::AttestationTierTooLow.into());
+    }
+    Ok(())
+}
+
+pub fn check_token_gate(
+    offer: &Offer,
+    gate_token_account: Option<&InterfaceAccount<TokenAccount>>,
+) -> Result<()> {
+    let Some(token_gate) = offer.token_gate else {
+        return Ok(());
+    };
+
+    let gate_token_account = gate_token_account.ok_or(ErrorCode::MissingGateTokenAccount)?;
+
+    if gate_token_account.mint != token_gate.mint
+        || gate_token_account.amount < token_gate.min_balance
+    {
+        return Err(ErrorCode::TokenGateNotMet.into());
+    }
+    Ok(())
+}
+
+/// Checks the taker isn't the offer's owner and, for collection bids, that the NFT
+/// they picked is one the maker wants.
+pub fn check_payment_mint(
+    offer: &Offer,
+    taker: &Pubkey,
+    token_mint_b: &InterfaceAccount<Mint>,
+    token_b_metadata: Option<&Account<MetadataAccount>>,
+) -> Result<()> {
+    if *taker == offer.current_owner() {
         return Err(ErrorCode::TakerShouldNotBeMaker.into());
     }
 
+    if let Some(collection) = offer.token_b_collection {
+        check_nft_mint(token_mint_b)?;
+        check_verified_collection(token_b_metadata, &token_mint_b.key(), &collection)?;
+    }
+    Ok(())
+}
+
+/// The amounts exchanged by one take of an offer.
+#[derive(Default)]
+pub struct Fill {
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    /// The basket legs the taker pays, on top of token B
+    pub basket_wanted: Vec<BasketLeg>,
+    /// The basket legs the recipient gets, on top of token A
+    pub basket_offered: Vec<BasketLeg>,
+}
+
+/// Checks how much of the offer the taker wants against the maker's fill policy, and
+/// prices it. `None` takes whatever is left of the offer.
+pub fn check_fill(
+    offer: &Offer,
+    token_mint_b: &Pubkey,
+    token_a_amount: Option<u64>,
+) -> Result<Fill> {
+    // The arbitration escrow holds a single token on each side, for the whole offer
+    if offer.arbitration.is_some() && (offer.is_basket() || offer.vesting.is_some()) {
+        return Err(ErrorCode::ArbitrationNotSupported.into());
+    }
+
+    let remaining = offer.token_a_remaining_amount();
+    let token_a_amount = token_a_amount.unwrap_or(remaining);
+
+    if token_a_amount > remaining || (token_a_amount == 0 && remaining > 0) {
+        return Err(ErrorCode::InvalidFillAmount.into());
+    }
+
+    if token_a_amount < remaining {
+        let FillPolicy::Partial { min_fill_amount } = offer.fill_policy else {
+            return Err(ErrorCode::PartialFillNotAllowed.into());
+        };
+
+        // Basket legs, vesting and arbitration are settled once, for the whole offer,
+        // and a collection bid is paid with a single NFT
+        if offer.is_basket()
+            || offer.vesting.is_some()
+            || offer.arbitration.is_some()
+            || offer.token_b_collection.is_some()
+        {
+            return Err(ErrorCode::PartialFillNotSupported.into());
+        }
+
+        if token_a_amount < min_fill_amount {
+            return Err(ErrorCode::FillBelowMinimum.into());
+        }
+
+        if remaining - token_a_amount < min_fill_amount {
+            return Err(ErrorCode::FillLeavesDust.into());
+        }
+    }
+
+    let token_b_amount = offer
+        .price_for_fill(token_mint_b, token_a_amount)
+        .ok_or(ErrorCode::TokenMintBNotAccepted)?;
+
+    Ok(Fill {
+        token_a_amount,
+        token_b_amount,
+        ..Fill::default()
+    })
+}
+
+pub fn send_tokens_from_taker_to_maker(ctx: &mut Context<TakeOffer>, fill: &Fill) -> Result<()> {
+    let amount = fill.token_b_amount;
+
+    // Arbitrated offers hold the payment, like token A, until the deal is settled
+    if let Some(terms) = ctx.accounts.offer.arbitration {
+        let (Some(arbitration), Some(arbitration_vault)) = (
+            ctx.accounts.arbitration.as_mut(),
+            ctx.accounts.arbitration_vault.as_deref(),
+        ) else {
+            return Err(ErrorCode::MissingArbitrationAccounts.into());
+        };
+
+        arbitration.set_inner(Arbitration {
+            offer: ctx.accounts.offer.key(),
+            maker: ctx.accounts.offer.current_owner(),
+            taker: ctx.accounts.taker.key(),
+            payout: ctx.accounts.payout.key(),
+            recipient: ctx.accounts.recipient.key(),
+            token_mint: ctx.accounts.token_mint_b.key(),
+            amount,
+            token_mint_a: ctx.accounts.token_mint_a.key(),
+            token_a_amount: fill.token_a_amount,
+            terms,
+            disputed: false,
+            deadline: Clock::get()?.unix_timestamp + terms.timeout_seconds,
+            rent_payer: ctx.accounts.payer.key(),
+            bump: ctx
+                .bumps
+                .arbitration
+                .ok_or(ErrorCode::MissingArbitrationAccounts)?,
+        });
+
+        return transfer_tokens(
+            &ctx.accounts.taker_token_account_b,
+            arbitration_vault,
+            &amount,
+            &ctx.accounts.token_mint_b,
+            &ctx.accounts.taker,
+            &ctx.accounts.token_program,
+        );
+    }
+
+    // Pay the maker directly, or park the tokens in the claim vault when the
+    // payout account can't receive them (e.g. it's frozen)
+    let to = match (
+        ctx.accounts.claim.as_mut(),
+        ctx.accounts.claim_vault.as_deref(),
+    ) {
+        (None, None) => either_token_account(
+            ctx.accounts.maker_token_account_b.as_deref(),
+            ctx.accounts.other_maker_token_account_b.as_deref(),
+        )?,
+        (Some(claim), Some(claim_vault))
+            if ctx.accounts.maker_token_account_b.is_none()
+                && ctx.accounts.other_maker_token_account_b.is_none() =>
+        {
+            claim.set_inner(Claim {
+                offer: ctx.accounts.offer.key(),
+                fill_index: ctx.accounts.offer.fill_count,
+                maker: ctx.accounts.offer.current_owner(),
+                payout: ctx.accounts.payout.key(),
+                mint: ctx.accounts.token_mint_b.key(),
+                rent_payer: ctx.accounts.payer.key(),
+                bump: ctx
+                    .bumps
+                    .claim
+                    .ok_or(ErrorCode::InvalidSettlementAccounts)?,
+            });
+            claim_vault
+        }
+        _ => return Err(ErrorCode::InvalidSettlementAccounts.into()),
+    };
+
     transfer_tokens(
         &ctx.accounts.taker_token_account_b,
-        &ctx.accounts.maker_token_account_b,
-        &ctx.accounts.offer.token_b_amount_wanted,
+        to,
+        &amount,
         &ctx.accounts.token_mint_b,
         &ctx.accounts.taker,
         &ctx.accounts.token_program,
     )
 }
 
-pub fn withdraw_from_vault_and_close_it(ctx: Context<TakeOffer>) -> Result<()> {
+/// Settles the extra legs of a basket offer. `remaining_accounts` holds, for each
+/// wanted leg, `[mint, taker token account, payout token account]`, followed by, for
+/// each offered leg, `[mint, leg vault, recipient token account]`. They were checked
+/// by `validate_take`.
+pub fn settle_basket<'info>(ctx: &Context<'_, '_, 'info, 'info, TakeOffer<'info>>) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+
+    let (wanted_accounts, offered_accounts) = ctx
+        .remaining_accounts
+        .split_at(3 * offer.basket_wanted.len());
+
+    for (leg, accounts) in offer.basket_wanted.iter().zip(wanted_accounts.chunks(3)) {
+        let leg_accounts = load_basket_leg(accounts, leg)?;
+
+        transfer_tokens(
+            &leg_accounts.from,
+            &leg_accounts.to,
+            &leg.amount,
+            &leg_accounts.mint,
+            &ctx.accounts.taker,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    let seeds = [
+        b"offer",
+        ctx.accounts.maker.key.as_ref(),
+        &offer.id.to_le_bytes(),
+        &[offer.bump],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    for (leg, accounts) in offer.basket_offered.iter().zip(offered_accounts.chunks(3)) {
+        let leg_accounts = load_basket_leg(accounts, leg)?;
+
+        transfer_tokens_with_signer(
+            &leg_accounts.from,
+            &leg_accounts.to,
+            &leg_accounts.from.amount,
+            &leg_accounts.mint,
+            &offer.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+
+        close_token_account(
+            &leg_accounts.from,
+            &ctx.accounts.rent_payer.to_account_info(),
+            &offer.to_account_info(),
+            &signer_seeds,
+            &ctx.accounts.token_program,
+        )?;
+    }
+
+    Ok(())
+}
+
+pub fn emit_offer_taken(
+    ctx: &Context<TakeOffer>,
+    fill: &Fill,
+    preimage: Option<[u8; 32]>,
+) -> Result<()> {
+    let offer = &ctx.accounts.offer;
+
+    emit!(OfferTaken {
+        offer: offer.key(),
+        maker: offer.maker,
+        owner: offer.current_owner(),
+        taker: ctx.accounts.taker.key(),
+        token_mint_a: offer.token_mint_a,
+        token_a_amount: fill.token_a_amount,
+        token_a_remaining_amount: offer.token_a_remaining_amount() - fill.token_a_amount,
+        token_mint_b: ctx.accounts.token_mint_b.key(),
+        token_b_amount: fill.token_b_amount,
+        // Only reveal the secret when it actually unlocked the offer
+        preimage: offer.hashlock.and(preimage),
+    });
+    Ok(())
+}
+
+pub fn save_vesting(ctx: &mut Context<TakeOffer>, fill: &Fill) -> Result<()> {
+    let Some(schedule) = ctx.accounts.offer.vesting else {
+        return Ok(());
+    };
+
+    let vesting = ctx
+        .accounts
+        .vesting
+        .as_mut()
+        .ok_or(ErrorCode::MissingVestingAccounts)?;
+    let now = Clock::get()?.unix_timestamp;
+
+    vesting.set_inner(Vesting {
+        offer: ctx.accounts.offer.key(),
+        beneficiary: ctx.accounts.recipient.key(),
+        token_mint: ctx.accounts.token_mint_a.key(),
+        total_amount: fill.token_a_amount,
+        claimed_amount: 0,
+        start_at: now,
+        cliff_at: now + schedule.cliff_seconds,
+        end_at: now + schedule.duration_seconds,
+        rent_payer: ctx.accounts.payer.key(),
+        bump: ctx.bumps.vesting.ok_or(ErrorCode::MissingVestingAccounts)?,
+    });
+    Ok(())
+}
+
+/// Where token A goes: the arbitration vault for arbitrated offers, the vesting
+/// vault for vesting offers, the recipient's token account otherwise.
+fn token_a_destination<'a, 'info>(
+    accounts: &'a TakeOffer<'info>,
+) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+    if accounts.offer.arbitration.is_some() {
+        return accounts
+            .arbitration_vault_a
+            .as_deref()
+            .ok_or_else(|| ErrorCode::MissingArbitrationAccounts.into());
+    }
+
+    if accounts.offer.vesting.is_some() {
+        return accounts
+            .vesting_vault
+            .as_deref()
+            .ok_or_else(|| ErrorCode::MissingVestingAccounts.into());
+    }
+
+    either_token_account(
+        accounts.taker_token_account_a.as_deref(),
+        accounts.other_taker_token_account_a.as_deref(),
+    )
+}
+
+pub fn send_offered_tokens_to_taker(ctx: &mut Context<TakeOffer>, fill: &Fill) -> Result<()> {
+    match ctx.accounts.offer.custody {
+        Custody::Vault => withdraw_from_vault_and_close_it(ctx, fill),
+        Custody::Delegate => pull_from_maker_through_delegate(ctx, fill),
+    }
+}
+
+/// Records the fill, and closes the offer once nothing is left of it.
+pub fn record_fill(ctx: Context<TakeOffer>, fill: &Fill) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+    offer.token_a_filled_amount += fill.token_a_amount;
+    offer.fill_count += 1;
+
+    if offer.token_a_remaining_amount() > 0 {
+        return Ok(());
+    }
+
+    offer.close(ctx.accounts.rent_payer.to_account_info())
+}
+
+fn withdraw_from_vault_and_close_it(ctx: &mut Context<TakeOffer>, fill: &Fill) -> Result<()> {
+    let is_last_fill = fill.token_a_amount == ctx.accounts.offer.token_a_remaining_amount();
+    let taker_token_account_a = token_a_destination(ctx.accounts)?.to_account_info();
+
+    let vault = ctx
+        .accounts
+        .vault
+        .as_mut()
+        .ok_or(ErrorCode::MissingCustodyAccount)?;
+
     // Transfer tokens held by vault token account (which is PDA for token_mint_a and maker) to taker's token account
     let seeds = &[
         b"offer",
@@ -36,8 +580,8 @@ pub fn withdraw_from_vault_and_close_it(ctx: Context<TakeOffer>) -> Result<()> {
     let signer_seeds = [&seeds[..]];
 
     let accounts = TransferChecked {
-        from: ctx.accounts.vault.to_account_info(),
-        to: ctx.accounts.taker_token_account_a.to_account_info(),
+        from: vault.to_account_info(),
+        to: taker_token_account_a,
         authority: ctx.accounts.offer.to_account_info(),
         mint: ctx.accounts.token_mint_a.to_account_info(),
     };
@@ -48,19 +592,26 @@ pub fn withdraw_from_vault_and_close_it(ctx: Context<TakeOffer>) -> Result<()> {
         &signer_seeds,
     );
 
-    ctx.accounts.vault.reload()?;
+    // The last fill empties the vault so it can be closed
+    let amount = if is_last_fill {
+        vault.reload()?;
+        vault.amount
+    } else {
+        fill.token_a_amount
+    };
 
-    transfer_checked(
-        cpi_context,
-        ctx.accounts.vault.amount,
-        ctx.accounts.token_mint_a.decimals,
-    )?;
+    transfer_checked(cpi_context, amount, ctx.accounts.token_mint_a.decimals)?;
+
+    // Partially filled offers keep their vault for the next takers
+    if !is_last_fill {
+        return Ok(());
+    }
 
     // Vault can be closed safely now
     let accounts = CloseAccount {
-        account: ctx.accounts.vault.to_account_info(),
+        account: vault.to_account_info(),
         authority: ctx.accounts.offer.to_account_info(),
-        destination: ctx.accounts.maker.to_account_info(),
+        destination: ctx.accounts.rent_payer.to_account_info(),
     };
 
     let cpi_context = CpiContext::new_with_signer(
@@ -72,92 +623,279 @@ pub fn withdraw_from_vault_and_close_it(ctx: Context<TakeOffer>) -> Result<()> {
     close_account(cpi_context)
 }
 
+fn pull_from_maker_through_delegate(ctx: &Context<TakeOffer>, fill: &Fill) -> Result<()> {
+    let (Some(maker_token_account_a), Some(delegate)) = (
+        ctx.accounts.maker_token_account_a.as_ref(),
+        ctx.accounts.delegate.as_ref(),
+    ) else {
+        return Err(ErrorCode::MissingCustodyAccount.into());
+    };
+
+    // The allowance was checked by `validate_take`
+    let amount = fill.token_a_amount;
+
+    let seeds = [
+        b"delegate",
+        ctx.accounts.maker.key.as_ref(),
+        &[ctx.bumps.delegate.ok_or(ErrorCode::MissingCustodyAccount)?],
+    ];
+    let signer_seeds = [&seeds[..]];
+
+    transfer_tokens_with_signer(
+        maker_token_account_a,
+        token_a_destination(ctx.accounts)?,
+        &amount,
+        &ctx.accounts.token_mint_a,
+        &delegate.to_account_info(),
+        &signer_seeds,
+        &ctx.accounts.token_program,
+    )
+}
+
 /// The `TakeOffer` struct defines the accounts required to accept an existing offer.
 ///
 /// This instruction will transfer the tokens from the taker to the maker and
-/// release the maker's tokens from the vault to the taker. It closes the offer
-/// and vault accounts, returning the rent to the maker.
+/// release the maker's tokens to the taker, either from the vault or, for
+/// non-custodial offers, from the maker's token account through the `delegate` PDA.
+/// It closes the offer and vault accounts, returning the rent to whoever paid for them.
+/// Offers with a `FillPolicy::Partial` can also be taken in parts; they are only
+/// closed once completely filled, and each take pays its share of the price.
+/// Hash-locked offers also need the secret whose SHA-256 hash was set at `make_offer`.
 #[derive(Accounts)]
 pub struct TakeOffer<'info> {
     /// The person accepting the offer. They must be a `Signer` to authorize the transaction.
-    /// This account will pay for any new accounts created.
-    #[account(mut)]
     pub taker: Signer<'info>,
 
-    /// The person who originally made the offer. They are a `SystemAccount` because
-    /// their primary role here is to receive a token transfer and the rent from
-    /// the closed accounts. The `has_one = maker` constraint on the `offer` account
-    /// ensures this is the correct maker.
+    /// The owner of the account receiving the offered tokens. Usually the `taker`
+    /// itself, but it can be any wallet or custody account the taker chooses.
+    /// CHECK: only used as the authority of the token account receiving `token_mint_a`.
+    pub recipient: UncheckedAccount<'info>,
+
+    /// The account paying for any new accounts created. It can be the `taker` itself
+    /// or a relayer sponsoring the transaction.
     #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The person who originally made the offer. They are a `SystemAccount` because
+    /// their key is only needed to derive the `offer` PDA. The `has_one = maker`
+    /// constraint on the `offer` account ensures this is the correct maker.
     pub maker: SystemAccount<'info>,
 
+    /// The owner of the account receiving the taker's payment, as set by the maker or,
+    /// once the offer is transferred, by its new owner.
+    /// `validate_take` checks it's the offer's `payout`.
+    /// CHECK: only used as the authority of the token account receiving `token_mint_b`.
+    pub payout: UncheckedAccount<'info>,
+
+    /// Whoever paid the rent for the `offer` and `vault` when the offer was made.
+    /// The rent of the closed accounts is returned to them. `validate_take` checks
+    /// it's the offer's `rent_payer`.
+    #[account(mut)]
+    pub rent_payer: SystemAccount<'info>,
+
     /// The token the maker was offering; taker will take this token essentially
     #[account(mint::token_program = token_program)]
     pub token_mint_a: InterfaceAccount<'info, Mint>,
 
-    /// The token the maker want in return; maker will get this token essentially
+    /// The token the maker want in return; maker will get this token essentially.
+    /// It can be `token_mint_b` or any of the offer's `payment_options`, at its own price.
+    /// For collection bids, this is the NFT the taker chose to deliver.
     #[account(mint::token_program = token_program)]
     pub token_mint_b: InterfaceAccount<'info, Mint>,
 
-    /// The taker's token account for `token_mint_a`. This is where the tokens from
-    /// the vault will be transferred to. `init_if_needed` means Anchor will create
-    /// this account if it doesn't already exist. The `taker` pays for the rent.
+    /// The token metadata account of `token_mint_b`. Only needed for collection bids.
+    pub token_b_metadata: Option<Box<Account<'info, MetadataAccount>>>,
+
+    /// The recipient's Associated Token Account (ATA) for `token_mint_a`. This is where
+    /// the tokens from the vault will be transferred to. `init_if_needed` means Anchor
+    /// will create this account if it doesn't already exist. The `payer` pays for the rent.
+    /// Leave it out when passing `other_taker_token_account_a`.
     #[account(
         init_if_needed,
-        payer = taker,
+        payer = payer,
         associated_token::mint = token_mint_a,
-        associated_token::authority = taker,
+        associated_token::authority = recipient,
         associated_token::token_program = token_program
     )]
-    pub taker_token_account_a: InterfaceAccount<'info, TokenAccount>,
+    pub taker_token_account_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    /// The taker's token account for `token_mint_b`. This is where the taker's
-    /// tokens will be transferred from to pay the maker.
+    /// Any other existing token account of the recipient for `token_mint_a`, for funds
+    /// that don't live in an ATA. Used instead of `taker_token_account_a`.
     #[account(
         mut,
-        associated_token::mint = token_mint_b,
-        associated_token::authority = taker,
-        associated_token::token_program = token_program
+        token::mint = token_mint_a,
+        token::authority = recipient,
+        token::token_program = token_program
+    )]
+    pub other_taker_token_account_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The taker's token account for `token_mint_b`, ATA or not. This is where the
+    /// taker's tokens will be transferred from to pay the maker.
+    #[account(
+        mut,
+        token::mint = token_mint_b,
+        token::authority = taker,
+        token::token_program = token_program
     )]
-    pub taker_token_account_b: InterfaceAccount<'info, TokenAccount>,
+    pub taker_token_account_b: Box<InterfaceAccount<'info, TokenAccount>>,
 
-    /// The maker's token account for `token_mint_b`. This is where the taker's
+    /// The payout wallet's token account for `token_mint_b`. This is where the taker's
     /// tokens `token_mint_b` will be transferred. `init_if_needed` ensures the maker doesn't need
     /// to have this account ready beforehand; it will be created if necessary.
+    /// Leave it out when passing `other_maker_token_account_b`, or to settle through
+    /// `claim` and `claim_vault` instead.
     #[account(
         init_if_needed,
-        payer = taker,
+        payer = payer,
         associated_token::mint = token_mint_b,
-        associated_token::authority = maker,
+        associated_token::authority = payout,
         associated_token::token_program = token_program,
     )]
-    pub maker_token_account_b: InterfaceAccount<'info, TokenAccount>,
+    pub maker_token_account_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Any other existing token account of the payout wallet for `token_mint_b`, for
+    /// funds that don't live in an ATA (e.g. a multisig's custody account).
+    #[account(
+        mut,
+        token::mint = token_mint_b,
+        token::authority = payout,
+        token::token_program = token_program
+    )]
+    pub other_maker_token_account_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Records the proceeds waiting for the maker when the payout account can't
+    /// receive them (frozen, or not creatable). The maker collects them later with
+    /// `claim_proceeds`. Seeded by the offer's `fill_count`, so each take of a
+    /// partially fillable offer gets its own. The `payer` pays for the rent.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Claim::INIT_SPACE,
+        seeds = [b"claim", offer.key().as_ref(), offer.fill_count.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub claim: Option<Box<Account<'info, Claim>>>,
+
+    /// The claim vault holding the taker's `token_mint_b` tokens, owned by the `claim` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = claim,
+        associated_token::token_program = token_program
+    )]
+    pub claim_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The offer account itself. It is marked `mut` because its state will change,
-    /// and `close` will remove it from the blockchain, returning its rent to the `maker`.
+    /// and it's closed once completely filled, returning its rent to the `rent_payer`.
     /// The `has_one` and `seeds` constraints are used to securely verify that this
     /// is the correct and valid offer PDA.
     #[account(
         mut,
-        close = maker,
         has_one = maker,
         has_one = token_mint_a,
-        has_one = token_mint_b,
+        constraint = offer.price_in(&token_mint_b.key()).is_some() @ ErrorCode::TokenMintBNotAccepted,
         seeds = [b"offer", maker.key().as_ref(), offer.id.to_le_bytes().as_ref()],
         bump = offer.bump,
     )]
     pub offer: Account<'info, Offer>,
 
     /// The vault token account holding the tokens from the maker. This is where
-    /// the tokens will be taken from. It is closed after the transfer, returning its
-    /// rent to the maker.
+    /// the tokens will be taken from. It is closed after the last fill, returning its
+    /// rent to the `rent_payer`. Only needed for `Custody::Vault` offers.
     #[account(
         mut,
         associated_token::mint = token_mint_a,
         associated_token::authority = offer,
         associated_token::token_program = token_program
     )]
-    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The maker's token account for `token_mint_a`, ATA or not, where the tokens of a
+    /// `Custody::Delegate` offer are pulled from.
+    #[account(
+        mut,
+        token::mint = token_mint_a,
+        token::authority = maker,
+        token::token_program = token_program
+    )]
+    pub maker_token_account_a: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// The program-owned PDA the maker approved on `maker_token_account_a`.
+    /// Only needed for `Custody::Delegate` offers.
+    /// CHECK: only used as a signer through its seeds; it holds no data.
+    #[account(seeds = [b"delegate", maker.key().as_ref()], bump)]
+    pub delegate: Option<UncheckedAccount<'info>>,
+
+    /// Holds both sides of the deal when the offer is arbitrated, until the parties
+    /// release them or the arbiter resolves a dispute. The `payer` pays for the rent.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Arbitration::INIT_SPACE,
+        seeds = [b"arbitration", offer.key().as_ref()],
+        bump
+    )]
+    pub arbitration: Option<Box<Account<'info, Arbitration>>>,
+
+    /// The arbitration vault holding the taker's payment, owned by the `arbitration` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = token_mint_b,
+        associated_token::authority = arbitration,
+        associated_token::token_program = token_program
+    )]
+    pub arbitration_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The arbitration vault holding token A for the recipient, owned by the
+    /// `arbitration` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = arbitration,
+        associated_token::token_program = token_program
+    )]
+    pub arbitration_vault_a: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Holds token A for the recipient when the offer vests. The recipient collects
+    /// it over time with `claim_vested`. The `payer` pays for the rent.
+    #[account(
+        init,
+        payer = payer,
+        space = (ANCHOR_DISCRIMINATOR as usize) + Vesting::INIT_SPACE,
+        seeds = [b"vesting", offer.key().as_ref()],
+        bump
+    )]
+    pub vesting: Option<Box<Account<'info, Vesting>>>,
+
+    /// The vesting vault holding token A, owned by the `vesting` PDA.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = token_mint_a,
+        associated_token::authority = vesting,
+        associated_token::token_program = token_program
+    )]
+    pub vesting_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The attestation authority registry. Only needed for offers requiring an attestation.
+    #[account(seeds = [b"registry"], bump = registry.bump)]
+    pub registry: Option<Box<Account<'info, Registry>>>,
+
+    /// The taker's attestation, issued by one of the registry's authorities.
+    /// Only needed for offers requiring an attestation.
+    #[account(
+        seeds = [b"attestation", attestation.authority.as_ref(), taker.key().as_ref()],
+        bump = attestation.bump,
+    )]
+    pub attestation: Option<Box<Account<'info, Attestation>>>,
+
+    /// A token account of the taker holding the offer's gate token. Only needed for
+    /// token-gated offers. It may belong to either token program.
+    #[account(token::authority = taker)]
+    pub gate_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The Solana Token Program. This is required for all token-related operations,
     /// such as transferring tokens.
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    error::ErrorCode,
+    events::OfferOwnershipTransferred,
+    state::{Custody, Offer},
+};
+
+pub fn save_offer_owner(
+    ctx: Context<TransferOfferOwnership>,
+    new_owner: Pubkey,
+    payout: Option<Pubkey>,
+) -> Result<()> {
+    let offer = &mut ctx.accounts.offer;
+
+    // The tokens of a non-custodial offer never leave the maker's account
+    if offer.custody == Custody::Delegate {
+        return Err(ErrorCode::OwnershipNotTransferable.into());
+    }
+
+    let previous_owner = offer.current_owner();
+
+    offer.owner = (new_owner != offer.maker).then_some(new_owner);
+    offer.payout = payout.unwrap_or(new_owner);
+
+    // The manager was the previous owner's key
+    offer.manager = None;
+    offer.manager_expires_at = 0;
+
+    emit!(OfferOwnershipTransferred {
+        offer: offer.key(),
+        maker: offer.maker,
+        previous_owner,
+        new_owner,
+    });
+    Ok(())
+}
+
+/// The `TransferOfferOwnership` struct defines the accounts required to hand a live
+/// offer over to another owner, without unwinding it.
+///
+/// The offer keeps its address, still seeded by the original maker and id, and its
+/// vault. From then on the new owner controls it: they get the taker's payment (or
+/// the `payout` wallet they pick), get the tokens back on cancel, and are the only
+/// one who can change its settings or transfer it again. The manager is revoked.
+#[derive(Accounts)]
+pub struct TransferOfferOwnership<'info> {
+    /// The current owner of the offer: the maker, unless it was transferred before.
+    pub owner: Signer<'info>,
+
+    /// The offer being transferred. The owner and `seeds` constraints are used to
+    /// securely verify that this is the correct and valid offer PDA.
+    #[account(
+        mut,
+        constraint = offer.current_owner() == owner.key() @ ErrorCode::NotOfferOwner,
+        seeds = [b"offer", offer.maker.as_ref(), offer.id.to_le_bytes().as_ref()],
+        bump = offer.bump,
+    )]
+    pub offer: Account<'info, Offer>,
+}
@@ -2,13 +2,20 @@
 
 pub mod constants;
 pub mod error;
+pub mod events;
 pub mod instructions;
+pub mod math;
 pub mod state;
 pub mod utils;
 
 use anchor_lang::prelude::*;
 
 pub use instructions::*;
+pub use math::Price;
+pub use state::{
+    ArbitrationTerms, DisputeOutcome, FillPolicy, MilestoneTerms, SignedOrder, TokenGate,
+    VestingSchedule,
+};
 
 declare_id!("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps");
 
@@ -21,17 +28,294 @@ pub mod escrow_app {
         offer_id: u64,
         token_a_offered_amount: u64,
         token_b_amount_wanted: u64,
+        payout: Option<Pubkey>,
+        hashlock: Option<[u8; 32]>,
     ) -> Result<()> {
         instructions::make_offer::send_offered_tokens_to_vault(&ctx, token_a_offered_amount)?;
-        instructions::make_offer::save_offer(ctx, offer_id, token_b_amount_wanted)
+        instructions::make_offer::save_offer(
+            ctx,
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout,
+            hashlock,
+        )
     }
 
-    pub fn take_offer(ctx: Context<TakeOffer>) -> Result<()> {
-        instructions::take_offer::send_tokens_from_taker_to_maker(&ctx)?;
-        instructions::take_offer::withdraw_from_vault_and_close_it(ctx)
+    pub fn make_approval_offer(
+        ctx: Context<MakeApprovalOffer>,
+        offer_id: u64,
+        token_a_offered_amount: u64,
+        token_b_amount_wanted: u64,
+    ) -> Result<()> {
+        instructions::make_approval_offer::save_approval_offer(
+            ctx,
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+        )
+    }
+
+    pub fn make_nft_offer(
+        ctx: Context<MakeNftOffer>,
+        offer_id: u64,
+        token_b_amount_wanted: u64,
+        collection: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::make_nft_offer::send_nft_to_vault(&ctx, collection)?;
+        instructions::make_nft_offer::save_nft_offer(
+            ctx,
+            offer_id,
+            token_b_amount_wanted,
+            collection,
+        )
+    }
+
+    pub fn make_collection_bid(
+        ctx: Context<MakeCollectionBid>,
+        offer_id: u64,
+        token_a_offered_amount: u64,
+        collection: Pubkey,
+    ) -> Result<()> {
+        instructions::make_collection_bid::send_offered_tokens_to_vault(
+            &ctx,
+            token_a_offered_amount,
+        )?;
+        instructions::make_collection_bid::save_collection_bid(
+            ctx,
+            offer_id,
+            token_a_offered_amount,
+            collection,
+        )
+    }
+
+    pub fn add_offered_leg(ctx: Context<AddOfferedLeg>, amount: u64) -> Result<()> {
+        instructions::add_offered_leg::send_leg_tokens_to_vault(&ctx, amount)?;
+        instructions::add_offered_leg::save_offered_leg(ctx, amount)
+    }
+
+    pub fn add_wanted_leg(ctx: Context<AddWantedLeg>, amount: u64) -> Result<()> {
+        instructions::add_wanted_leg::save_wanted_leg(ctx, amount)
+    }
+
+    pub fn add_payment_option(ctx: Context<AddPaymentOption>, amount: u64) -> Result<()> {
+        instructions::add_payment_option::save_payment_option(ctx, amount)
+    }
+
+    pub fn take_offer<'info>(
+        mut ctx: Context<'_, '_, 'info, 'info, TakeOffer<'info>>,
+        preimage: Option<[u8; 32]>,
+        token_a_amount: Option<u64>,
+    ) -> Result<()> {
+        let fill = instructions::take_offer::check_take(&ctx, preimage, token_a_amount)?;
+        instructions::take_offer::send_tokens_from_taker_to_maker(&mut ctx, &fill)?;
+        instructions::take_offer::settle_basket(&ctx)?;
+        instructions::take_offer::emit_offer_taken(&ctx, &fill, preimage)?;
+        instructions::take_offer::save_vesting(&mut ctx, &fill)?;
+        instructions::take_offer::send_offered_tokens_to_taker(&mut ctx, &fill)?;
+        instructions::take_offer::record_fill(ctx, &fill)
+    }
+
+    pub fn quote_take<'info>(
+        ctx: Context<'_, '_, 'info, 'info, QuoteTake<'info>>,
+        preimage: Option<[u8; 32]>,
+        token_a_amount: Option<u64>,
+    ) -> Result<TakeQuote> {
+        instructions::quote_take::quote(ctx, preimage, token_a_amount)
     }
 
-    pub fn cancel_offer(ctx: Context<CancelOffer>) -> Result<()> {
+    pub fn cancel_offer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CancelOffer<'info>>,
+    ) -> Result<()> {
+        instructions::cancel_offer::check_offer_authority(&ctx)?;
+        instructions::cancel_offer::check_offer_commitment(&ctx)?;
+        instructions::cancel_offer::return_basket_legs(&ctx)?;
         instructions::cancel_offer::withdraw_from_vault_and_close_it(ctx)
     }
+
+    pub fn transfer_offer_ownership(
+        ctx: Context<TransferOfferOwnership>,
+        new_owner: Pubkey,
+        payout: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::transfer_offer_ownership::save_offer_owner(ctx, new_owner, payout)
+    }
+
+    pub fn sweep(ctx: Context<Sweep>) -> Result<()> {
+        instructions::sweep::sweep_token_account(ctx)
+    }
+
+    pub fn claim_proceeds(ctx: Context<ClaimProceeds>) -> Result<()> {
+        instructions::claim_proceeds::withdraw_from_claim_vault_and_close_it(ctx)
+    }
+
+    pub fn replace_offer(
+        ctx: Context<ReplaceOffer>,
+        new_offer_id: u64,
+        token_a_offered_amount: u64,
+        token_b_amount_wanted: u64,
+    ) -> Result<()> {
+        instructions::replace_offer::check_offer_commitment(&ctx)?;
+        instructions::replace_offer::move_offered_tokens_to_new_vault(
+            &ctx,
+            token_a_offered_amount,
+        )?;
+        instructions::replace_offer::save_new_offer(
+            ctx,
+            new_offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+        )
+    }
+
+    pub fn migrate_offer(ctx: Context<MigrateOffer>) -> Result<()> {
+        instructions::migrate_offer::migrate_offer_account(ctx)
+    }
+
+    pub fn set_offer_manager(
+        ctx: Context<SetOfferManager>,
+        manager: Option<Pubkey>,
+        manager_expires_at: i64,
+    ) -> Result<()> {
+        instructions::set_offer_manager::save_offer_manager(ctx, manager, manager_expires_at)
+    }
+
+    pub fn set_offer_fill_policy(
+        ctx: Context<SetOfferFillPolicy>,
+        fill_policy: FillPolicy,
+    ) -> Result<()> {
+        instructions::set_offer_fill_policy::save_offer_fill_policy(ctx, fill_policy)
+    }
+
+    pub fn set_offer_commitment(
+        ctx: Context<SetOfferCommitment>,
+        min_live_until: i64,
+    ) -> Result<()> {
+        instructions::set_offer_commitment::save_offer_commitment(ctx, min_live_until)
+    }
+
+    pub fn lock_htlc(
+        ctx: Context<LockHtlc>,
+        htlc_id: u64,
+        counterparty: Pubkey,
+        amount: u64,
+        hashlock: [u8; 32],
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::lock_htlc::send_locked_tokens_to_vault(&ctx, amount, expires_at)?;
+        instructions::lock_htlc::save_htlc(ctx, htlc_id, counterparty, amount, hashlock, expires_at)
+    }
+
+    pub fn claim_htlc(ctx: Context<ClaimHtlc>, preimage: [u8; 32]) -> Result<()> {
+        instructions::claim_htlc::check_preimage(&ctx, preimage)?;
+        instructions::claim_htlc::withdraw_from_vault_and_close_it(ctx)
+    }
+
+    pub fn refund_htlc(ctx: Context<RefundHtlc>) -> Result<()> {
+        instructions::refund_htlc::check_htlc_expired(&ctx)?;
+        instructions::refund_htlc::withdraw_from_vault_and_close_it(ctx)
+    }
+
+    pub fn lock_milestones(
+        ctx: Context<LockMilestones>,
+        escrow_id: u64,
+        beneficiary: Pubkey,
+        milestones: Vec<MilestoneTerms>,
+    ) -> Result<()> {
+        instructions::lock_milestones::send_locked_tokens_to_vault(&ctx, &milestones)?;
+        instructions::lock_milestones::save_milestone_escrow(
+            ctx,
+            escrow_id,
+            beneficiary,
+            milestones,
+        )
+    }
+
+    pub fn release_milestone(mut ctx: Context<ReleaseMilestone>, index: u8) -> Result<()> {
+        let amount = instructions::release_milestone::mark_milestone_released(&mut ctx, index)?;
+        instructions::release_milestone::send_milestone_to_beneficiary(ctx, amount)
+    }
+
+    pub fn set_offer_attestation(
+        ctx: Context<SetOfferAttestation>,
+        min_attestation_tier: Option<u8>,
+    ) -> Result<()> {
+        instructions::set_offer_attestation::save_offer_attestation(ctx, min_attestation_tier)
+    }
+
+    pub fn set_offer_gate(ctx: Context<SetOfferGate>, token_gate: Option<TokenGate>) -> Result<()> {
+        instructions::set_offer_gate::save_offer_gate(ctx, token_gate)
+    }
+
+    pub fn set_offer_vesting(
+        ctx: Context<SetOfferVesting>,
+        vesting: Option<VestingSchedule>,
+    ) -> Result<()> {
+        instructions::set_offer_vesting::save_offer_vesting(ctx, vesting)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        instructions::claim_vested::release_unlocked_tokens(ctx)
+    }
+
+    pub fn set_offer_arbitration(
+        ctx: Context<SetOfferArbitration>,
+        arbitration: Option<ArbitrationTerms>,
+    ) -> Result<()> {
+        instructions::set_offer_arbitration::save_offer_arbitration(ctx, arbitration)
+    }
+
+    pub fn raise_dispute(ctx: Context<RaiseDispute>) -> Result<()> {
+        instructions::raise_dispute::save_dispute(ctx)
+    }
+
+    pub fn release_arbitration(ctx: Context<SettleArbitration>, to_maker: bool) -> Result<()> {
+        let (maker_share_bps, with_fee) =
+            instructions::settle_arbitration::check_release(&ctx, to_maker)?;
+        instructions::settle_arbitration::pay_out_and_close_vaults(ctx, maker_share_bps, with_fee)
+    }
+
+    pub fn resolve_dispute(ctx: Context<SettleArbitration>, maker_share_bps: u16) -> Result<()> {
+        let (maker_share_bps, with_fee) =
+            instructions::settle_arbitration::check_resolution(&ctx, maker_share_bps)?;
+        instructions::settle_arbitration::pay_out_and_close_vaults(ctx, maker_share_bps, with_fee)
+    }
+
+    pub fn settle_expired_arbitration(ctx: Context<SettleArbitration>) -> Result<()> {
+        let (maker_share_bps, with_fee) = instructions::settle_arbitration::check_expired(&ctx)?;
+        instructions::settle_arbitration::pay_out_and_close_vaults(ctx, maker_share_bps, with_fee)
+    }
+
+    pub fn init_registry(ctx: Context<InitRegistry>) -> Result<()> {
+        instructions::init_registry::save_registry(ctx)
+    }
+
+    pub fn set_registry_authorities(
+        ctx: Context<SetRegistryAuthorities>,
+        authorities: Vec<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_registry_authorities::save_registry_authorities(ctx, authorities)
+    }
+
+    pub fn issue_attestation(
+        ctx: Context<IssueAttestation>,
+        tier: u8,
+        expires_at: i64,
+    ) -> Result<()> {
+        instructions::issue_attestation::save_attestation(ctx, tier, expires_at)
+    }
+
+    pub fn revoke_attestation(_ctx: Context<RevokeAttestation>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn fill_signed_order(ctx: Context<FillSignedOrder>, order: SignedOrder) -> Result<()> {
+        instructions::fill_signed_order::verify_signed_order(&ctx, &order)?;
+        instructions::fill_signed_order::send_tokens_from_taker_to_maker(&ctx, &order)?;
+        instructions::fill_signed_order::pull_tokens_from_maker_to_taker(ctx, &order)
+    }
+
+    pub fn close_order_nonce(ctx: Context<CloseOrderNonce>) -> Result<()> {
+        instructions::close_order_nonce::check_order_expired(&ctx)
+    }
 }
@@ -0,0 +1,64 @@
+//! Checked integer math shared by the pricing code. Every division states which way
+//! it rounds, so rounding always goes against the party that can't lose out.
+
+use std::cmp::Ordering;
+
+use anchor_lang::prelude::*;
+
+/// Which way a division rounds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rounding {
+    Down,
+    Up,
+}
+
+/// `a * b / c`, computed on 128 bits and rounded as asked. `None` if `c` is zero or
+/// the result doesn't fit a `u64`.
+pub fn checked_mul_div(a: u64, b: u64, c: u64, rounding: Rounding) -> Option<u64> {
+    if c == 0 {
+        return None;
+    }
+
+    let product = a as u128 * b as u128;
+    let quotient = match rounding {
+        Rounding::Down => product / c as u128,
+        Rounding::Up => product.div_ceil(c as u128),
+    };
+    u64::try_from(quotient).ok()
+}
+
+/// A price as a ratio: `numerator` of token B for `denominator` of token A. Quotes
+/// are exact on the whole offer and round up in the maker's favour on any part of it.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct Price {
+    pub numerator: u64,
+    pub denominator: u64,
+}
+
+impl Price {
+    /// Token B owed for `token_a_amount`, rounded up so the maker never gets less
+    /// than the ratio.
+    pub fn quote(&self, token_a_amount: u64) -> Option<u64> {
+        checked_mul_div(
+            token_a_amount,
+            self.numerator,
+            self.denominator,
+            Rounding::Up,
+        )
+    }
+
+    /// Token B owed for the next `token_a_amount` once `filled` is already paid for.
+    /// Rounding is done on the running total, so the parts always add up to the
+    /// `numerator` and never round up more than once.
+    pub fn quote_fill(&self, filled: u64, token_a_amount: u64) -> Option<u64> {
+        let paid_after = self.quote(filled.checked_add(token_a_amount)?)?;
+        paid_after.checked_sub(self.quote(filled)?)
+    }
+
+    /// Compares two prices exactly, without dividing: a higher price asks more
+    /// token B per token A.
+    pub fn compare(&self, other: &Price) -> Ordering {
+        (self.numerator as u128 * other.denominator as u128)
+            .cmp(&(other.numerator as u128 * self.denominator as u128))
+    }
+}
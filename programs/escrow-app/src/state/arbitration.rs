@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::MAX_BPS,
+    math::{checked_mul_div, Rounding},
+};
+
+/// What happens to the held payment when nobody settles it before the deadline.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum DisputeOutcome {
+    /// The payment goes to the maker
+    Release,
+    /// The payment goes back to the taker
+    Refund,
+}
+
+/// Arbitration terms of an offer, set by the maker.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct ArbitrationTerms {
+    pub arbiter: Pubkey,
+    /// Share of the payment paid to the arbiter when it resolves a dispute, in basis points
+    pub fee_bps: u16,
+    /// Seconds the parties (or, once disputed, the arbiter) have before `default_outcome` applies
+    pub timeout_seconds: i64,
+    pub default_outcome: DisputeOutcome,
+}
+
+/// Both sides of an arbitrated deal: the taker's payment and the offered token A, each
+/// held in a vault (an ATA of this PDA) until the parties release them, the arbiter
+/// resolves a dispute, or the deadline passes.
+#[account]
+#[derive(InitSpace)]
+pub struct Arbitration {
+    /// The offer that was taken; it's closed by now but its key still seeds the arbitration
+    pub offer: Pubkey,
+    /// Who owned the offer when it was taken: the maker, unless it was transferred
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    /// Owner of the token account receiving what's released to the maker
+    pub payout: Pubkey,
+    /// Owner of the token account receiving what's released of token A
+    pub recipient: Pubkey,
+    /// The token the taker paid with, and how much
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    pub token_mint_a: Pubkey,
+    pub token_a_amount: u64,
+    pub terms: ArbitrationTerms,
+    pub disputed: bool,
+    /// Unix timestamp from which `terms.default_outcome` can be applied
+    pub deadline: i64,
+    /// Whoever paid the rent for the arbitration and its vault when the offer was taken
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+impl Arbitration {
+    /// Splits `amount` into the maker's, the taker's and the arbiter's shares. The fee
+    /// comes off the top, the rest is shared according to `maker_share_bps`. Both
+    /// round down, so the taker gets whatever rounding leaves over.
+    pub fn split(&self, maker_share_bps: u16, with_fee: bool) -> Option<(u64, u64, u64)> {
+        let fee = if with_fee {
+            checked_mul_div(
+                self.amount,
+                self.terms.fee_bps as u64,
+                MAX_BPS as u64,
+                Rounding::Down,
+            )?
+        } else {
+            0
+        };
+        let maker = checked_mul_div(
+            self.amount - fee,
+            maker_share_bps as u64,
+            MAX_BPS as u64,
+            Rounding::Down,
+        )?;
+        let taker = self.amount - fee - maker;
+
+        Some((maker, taker, fee))
+    }
+
+    /// Splits token A into the recipient's and the maker's shares. The recipient gets
+    /// `maker_share_bps` of it, the part of the deal that went through, rounded down;
+    /// the maker gets the rest back.
+    pub fn split_token_a(&self, maker_share_bps: u16) -> Option<(u64, u64)> {
+        let recipient = checked_mul_div(
+            self.token_a_amount,
+            maker_share_bps as u64,
+            MAX_BPS as u64,
+            Rounding::Down,
+        )?;
+
+        Some((recipient, self.token_a_amount - recipient))
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_ATTESTATION_AUTHORITIES;
+
+/// The program-wide list of authorities allowed to issue attestations, managed by
+/// the program admin.
+#[account]
+#[derive(InitSpace)]
+pub struct Registry {
+    pub admin: Pubkey,
+    #[max_len(MAX_ATTESTATION_AUTHORITIES)]
+    pub authorities: Vec<Pubkey>,
+    pub bump: u8,
+}
+
+/// Issued by an attestation authority to vouch for a wallet (e.g. after KYC).
+#[account]
+#[derive(InitSpace)]
+pub struct Attestation {
+    pub authority: Pubkey,
+    pub wallet: Pubkey,
+    /// Verification level, higher is stricter; its meaning is up to the authorities
+    pub tier: u8,
+    /// Unix timestamp from which the attestation is no longer valid
+    pub expires_at: i64,
+    /// Whoever paid the rent for the account; it goes back to them on revocation
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+impl Attestation {
+    /// Valid while its authority is still in the registry and it hasn't expired.
+    pub fn is_valid(&self, registry: &Registry, now: i64) -> bool {
+        registry.authorities.contains(&self.authority) && now < self.expires_at
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+/// Proceeds of a taken offer waiting in a claim vault (an ATA of this PDA) for the
+/// maker to collect them with `claim_proceeds`.
+#[account]
+#[derive(InitSpace)]
+pub struct Claim {
+    /// The offer that was taken; it's closed by now but its key still seeds the claim
+    pub offer: Pubkey,
+    /// Which take of the offer left these proceeds; it seeds the claim with `offer`
+    pub fill_index: u32,
+    /// Who owned the offer when it was taken: the maker, unless it was transferred
+    pub maker: Pubkey,
+    /// Owner of the token account the proceeds are released to
+    pub payout: Pubkey,
+    pub mint: Pubkey,
+    /// Whoever paid the rent for the claim and its vault when the offer was taken
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+/// A hash-time-locked contract: `amount` of `token_mint` locked in a vault (an ATA
+/// of this PDA) for `counterparty`, who can claim it with the secret behind
+/// `hashlock` until `expires_at`. After that, it can only be refunded to the maker.
+#[account]
+#[derive(InitSpace)]
+pub struct Htlc {
+    pub id: u64,
+    pub maker: Pubkey,
+    pub counterparty: Pubkey,
+    pub token_mint: Pubkey,
+    pub amount: u64,
+    /// SHA-256 hash of the secret that unlocks the tokens
+    pub hashlock: [u8; 32],
+    /// Unix timestamp from which the tokens can no longer be claimed, only refunded
+    pub expires_at: i64,
+    /// Whoever paid the rent for the `htlc` and its vault; it's returned to them on close
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+impl Htlc {
+    pub fn is_expired(&self, now: i64) -> bool {
+        now >= self.expires_at
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_MILESTONES;
+
+/// One stage of a milestone escrow, as given by the maker.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct MilestoneTerms {
+    pub amount: u64,
+    /// Unix timestamp from which anyone can release the milestone, not just the maker
+    pub release_at: i64,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct Milestone {
+    pub amount: u64,
+    pub release_at: i64,
+    pub released: bool,
+}
+
+/// Tokens locked by a maker for a fixed `beneficiary`, in a vault (an ATA of this
+/// PDA), and paid out one milestone at a time, e.g. for freelance work.
+#[account]
+#[derive(InitSpace)]
+pub struct MilestoneEscrow {
+    pub id: u64,
+    pub maker: Pubkey,
+    pub beneficiary: Pubkey,
+    pub token_mint: Pubkey,
+    #[max_len(MAX_MILESTONES)]
+    pub milestones: Vec<Milestone>,
+    /// Whoever paid the rent for the escrow and its vault; it's returned to them on close
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+impl MilestoneEscrow {
+    pub fn is_fully_released(&self) -> bool {
+        self.milestones.iter().all(|milestone| milestone.released)
+    }
+}
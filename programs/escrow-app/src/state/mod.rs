@@ -1,3 +1,19 @@
+pub mod arbitration;
+pub mod attestation;
+pub mod claim;
+pub mod htlc;
+pub mod milestone;
 pub mod offer;
+pub mod offer_v1;
+pub mod signed_order;
+pub mod vesting;
 
+pub use arbitration::*;
+pub use attestation::*;
+pub use claim::*;
+pub use htlc::*;
+pub use milestone::*;
 pub use offer::*;
+pub use offer_v1::*;
+pub use signed_order::*;
+pub use vesting::*;
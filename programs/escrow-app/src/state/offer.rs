@@ -1,12 +1,213 @@
 use anchor_lang::prelude::*;
 
+use crate::{
+    constants::{MAX_BASKET_LEGS, MAX_PAYMENT_OPTIONS, OFFER_RESERVED_BYTES, OFFER_VERSION},
+    math::Price,
+    state::{ArbitrationTerms, VestingSchedule},
+};
+
+/// Where the offered tokens are while the offer is live.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum Custody {
+    /// Deposited into the offer's `vault`
+    Vault,
+    /// Kept in the maker's token account, which approved the `delegate` PDA
+    Delegate,
+}
+
+/// One extra token of a basket offer, on top of `token_mint_a` / `token_mint_b`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct BasketLeg {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Another mint the taker can pay with, at its own price.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct PaymentOption {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+/// Restricts who can take an offer to holders of at least `min_balance` of `mint`
+/// (e.g. a community membership token).
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct TokenGate {
+    pub mint: Pubkey,
+    pub min_balance: u64,
+}
+
+/// How much of an offer a single take can fill.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub enum FillPolicy {
+    /// The whole offer has to be taken at once (fill-or-kill)
+    AllOrNothing,
+    /// Takers can fill part of the offer, at least `min_fill_amount` of token A at a
+    /// time, as long as they don't leave less than that behind
+    Partial { min_fill_amount: u64 },
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Offer {
+    /// Layout version of the account, `OFFER_VERSION` for accounts created or migrated
+    /// by this program version. Old accounts are upgraded with `migrate_offer`.
+    pub version: u8,
     pub id: u64,
     pub maker: Pubkey,
     pub token_mint_a: Pubkey,
     pub token_mint_b: Pubkey,
     pub token_b_amount_wanted: u64,
     pub bump: u8,
+    /// A key allowed to cancel the offer on behalf of the maker (e.g. a bot's session key)
+    pub manager: Option<Pubkey>,
+    /// Unix timestamp after which `manager` can no longer act on the offer
+    pub manager_expires_at: i64,
+    /// Whoever paid the rent for the `offer` and `vault`; it's returned to them on close
+    pub rent_payer: Pubkey,
+    pub custody: Custody,
+    pub token_a_offered_amount: u64,
+    /// Verified collection of the offered NFT, checked when the offer was made
+    pub token_a_collection: Option<Pubkey>,
+    /// When set, the taker can pay with any NFT of this verified collection
+    /// instead of `token_mint_b` (a collection bid)
+    pub token_b_collection: Option<Pubkey>,
+    /// Extra offered tokens, each held in its own vault (an ATA of the offer PDA)
+    #[max_len(MAX_BASKET_LEGS)]
+    pub basket_offered: Vec<BasketLeg>,
+    /// Extra tokens wanted in return
+    #[max_len(MAX_BASKET_LEGS)]
+    pub basket_wanted: Vec<BasketLeg>,
+    /// Mints accepted instead of `token_mint_b`, each with its own amount wanted
+    #[max_len(MAX_PAYMENT_OPTIONS)]
+    pub payment_options: Vec<PaymentOption>,
+    /// Owner of the token account receiving the taker's payment; the maker unless
+    /// another wallet (e.g. cold storage) was set at `make_offer`
+    pub payout: Pubkey,
+    /// SHA-256 hash of a secret the taker must reveal to take the offer
+    pub hashlock: Option<[u8; 32]>,
+    /// When set, only takers with a valid attestation of at least this tier can take the offer
+    pub min_attestation_tier: Option<u8>,
+    /// When set, only takers holding the gate token can take the offer
+    pub token_gate: Option<TokenGate>,
+    /// When set, token A vests for the taker instead of being sent on take
+    pub vesting: Option<VestingSchedule>,
+    /// When set, the taker's payment is held until released, or resolved by the arbiter
+    pub arbitration: Option<ArbitrationTerms>,
+    /// Unix timestamp until which the maker committed not to cancel or replace the offer
+    pub min_live_until: Option<i64>,
+    pub fill_policy: FillPolicy,
+    /// How much of `token_a_offered_amount` earlier partial takes already filled
+    pub token_a_filled_amount: u64,
+    /// Number of takes so far. A take leaving its payment in a claim seeds the claim
+    /// with it, so every partial fill gets its own.
+    pub fill_count: u32,
+    /// Who the offer was handed to with `transfer_offer_ownership`; `None` while the
+    /// maker still owns it. The PDA stays seeded by the `maker`.
+    pub owner: Option<Pubkey>,
+    /// Space kept for future fields, so adding one doesn't require a migration
+    pub reserved: [u8; OFFER_RESERVED_BYTES],
+}
+
+impl Offer {
+    /// An empty custodial offer with every optional term off, paying out to the maker.
+    /// The instructions making offers fill in the rest with struct update syntax, so
+    /// a new field only needs a default here.
+    pub fn new(id: u64, maker: Pubkey, rent_payer: Pubkey, bump: u8) -> Self {
+        Self {
+            version: OFFER_VERSION,
+            id,
+            maker,
+            token_mint_a: Pubkey::default(),
+            token_mint_b: Pubkey::default(),
+            token_b_amount_wanted: 0,
+            bump,
+            manager: None,
+            manager_expires_at: 0,
+            rent_payer,
+            custody: Custody::Vault,
+            token_a_offered_amount: 0,
+            token_a_collection: None,
+            token_b_collection: None,
+            basket_offered: Vec::new(),
+            basket_wanted: Vec::new(),
+            payment_options: Vec::new(),
+            payout: maker,
+            hashlock: None,
+            min_attestation_tier: None,
+            token_gate: None,
+            vesting: None,
+            arbitration: None,
+            min_live_until: None,
+            fill_policy: FillPolicy::AllOrNothing,
+            token_a_filled_amount: 0,
+            fill_count: 0,
+            owner: None,
+            reserved: [0; OFFER_RESERVED_BYTES],
+        }
+    }
+
+    pub fn is_basket(&self) -> bool {
+        !self.basket_offered.is_empty() || !self.basket_wanted.is_empty()
+    }
+
+    /// Number of `remaining_accounts` needed to settle the basket: three per leg.
+    pub fn basket_accounts_len(&self) -> usize {
+        3 * (self.basket_offered.len() + self.basket_wanted.len())
+    }
+
+    /// Amount of `mint` the taker has to pay, or `None` if the offer doesn't accept it.
+    /// Collection bids accept any mint here; the NFT itself is checked on take.
+    pub fn price_in(&self, mint: &Pubkey) -> Option<u64> {
+        if self.token_b_collection.is_some() || *mint == self.token_mint_b {
+            return Some(self.token_b_amount_wanted);
+        }
+        self.payment_options
+            .iter()
+            .find(|option| option.mint == *mint)
+            .map(|option| option.amount)
+    }
+
+    pub fn token_a_remaining_amount(&self) -> u64 {
+        self.token_a_offered_amount - self.token_a_filled_amount
+    }
+
+    /// The offer's price in `mint`, as a ratio to the whole of token A offered.
+    pub fn price(&self, mint: &Pubkey) -> Option<Price> {
+        Some(Price {
+            numerator: self.price_in(mint)?,
+            denominator: self.token_a_offered_amount,
+        })
+    }
+
+    /// Amount of `mint` the taker has to pay to fill `token_a_amount` more of the offer,
+    /// pro rata, rounded up in the maker's favour.
+    pub fn price_for_fill(&self, mint: &Pubkey, token_a_amount: u64) -> Option<u64> {
+        let price = self.price(mint)?;
+
+        // Nothing to prorate when no token A is offered (e.g. a pure basket offer)
+        if price.denominator == 0 {
+            return Some(price.numerator);
+        }
+        price.quote_fill(self.token_a_filled_amount, token_a_amount)
+    }
+
+    /// Whether the maker's commitment still keeps the offer from being cancelled or replaced.
+    pub fn is_committed(&self, now: i64) -> bool {
+        self.min_live_until.is_some_and(|until| now < until)
+    }
+
+    /// Who controls the offer and gets its tokens back on cancel: the maker, unless
+    /// the offer was transferred.
+    pub fn current_owner(&self) -> Pubkey {
+        self.owner.unwrap_or(self.maker)
+    }
+
+    /// The owner can always act on the offer, a manager only until it expires.
+    pub fn is_authority(&self, key: &Pubkey, now: i64) -> bool {
+        if *key == self.current_owner() {
+            return true;
+        }
+        self.manager == Some(*key) && now < self.manager_expires_at
+    }
 }
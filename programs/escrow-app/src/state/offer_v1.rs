@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::ANCHOR_DISCRIMINATOR, error::ErrorCode, state::Offer};
+
+/// The `Offer` layout before accounts were versioned. Only used to read old
+/// accounts in `migrate_offer`; it must never change.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct OfferV1 {
+    pub id: u64,
+    pub maker: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_b_amount_wanted: u64,
+    pub bump: u8,
+}
+
+impl OfferV1 {
+    /// Size of an account holding an `OfferV1`, discriminator included.
+    pub const ACCOUNT_SPACE: usize = ANCHOR_DISCRIMINATOR as usize + OfferV1::INIT_SPACE;
+
+    /// Reads an old offer account. It shares the discriminator with `Offer`, so the
+    /// account size is what tells the two layouts apart.
+    pub fn try_from_account_data(data: &[u8]) -> Result<OfferV1> {
+        if data.len() != Self::ACCOUNT_SPACE {
+            return Err(ErrorCode::OfferAlreadyMigrated.into());
+        }
+
+        if !data.starts_with(Offer::DISCRIMINATOR) {
+            return Err(anchor_lang::error::ErrorCode::AccountDiscriminatorMismatch.into());
+        }
+
+        Ok(OfferV1::deserialize(
+            &mut &data[ANCHOR_DISCRIMINATOR as usize..],
+        )?)
+    }
+
+    /// Carries the old fields over to the current layout; every newer field gets
+    /// the default of a plain `make_offer`. Old offers didn't record how much they
+    /// offer: the whole vault went to the taker, so `token_a_offered_amount` is
+    /// the vault balance.
+    pub fn into_offer(self, token_a_offered_amount: u64) -> Offer {
+        Offer {
+            token_mint_a: self.token_mint_a,
+            token_mint_b: self.token_mint_b,
+            token_b_amount_wanted: self.token_b_amount_wanted,
+            token_a_offered_amount,
+            // The maker paid the rent of old offers
+            ..Offer::new(self.id, self.maker, self.maker, self.bump)
+        }
+    }
+}
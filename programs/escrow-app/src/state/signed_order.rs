@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+/// Prefix of every signed order message, so a maker's signature over an order
+/// can never be replayed as something else.
+pub const SIGNED_ORDER_DOMAIN: &[u8] = b"escrow-app:signed-order:v1";
+
+/// An order signed off-chain by the maker. The taker (or a relayer) submits it in
+/// `fill_signed_order` together with an Ed25519 program instruction carrying the
+/// maker's signature over `SignedOrder::message`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct SignedOrder {
+    pub program_id: Pubkey,
+    pub maker: Pubkey,
+    pub token_mint_a: Pubkey,
+    pub token_mint_b: Pubkey,
+    pub token_a_amount: u64,
+    pub token_b_amount: u64,
+    pub expires_at: i64,
+    pub nonce: u64,
+}
+
+impl SignedOrder {
+    /// The canonical bytes the maker signs: the domain prefix followed by the
+    /// borsh encoding of the order.
+    pub fn message(&self) -> Result<Vec<u8>> {
+        let mut message = SIGNED_ORDER_DOMAIN.to_vec();
+        self.serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+/// Marks a signed order nonce as used. It is created when the order is filled,
+/// so the same signed order can't be filled twice. Once the order has expired it
+/// can't be filled anyway, and `close_order_nonce` returns the rent.
+#[account]
+#[derive(InitSpace)]
+pub struct OrderNonce {
+    pub maker: Pubkey,
+    pub nonce: u64,
+    pub expires_at: i64,
+    /// Whoever paid the rent for this account when the order was filled.
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
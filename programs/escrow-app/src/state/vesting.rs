@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::math::{checked_mul_div, Rounding};
+
+/// Vesting terms of an offer, relative to the moment it's taken.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq, InitSpace)]
+pub struct VestingSchedule {
+    /// Seconds after the fill before anything can be claimed
+    pub cliff_seconds: i64,
+    /// Seconds after the fill until everything is unlocked
+    pub duration_seconds: i64,
+}
+
+/// Token A bought from a vesting offer, held in a vault (an ATA of this PDA) and
+/// released linearly to the beneficiary with `claim_vested`.
+#[account]
+#[derive(InitSpace)]
+pub struct Vesting {
+    /// The offer that was taken; it's closed by now but its key still seeds the vesting
+    pub offer: Pubkey,
+    pub beneficiary: Pubkey,
+    pub token_mint: Pubkey,
+    pub total_amount: u64,
+    pub claimed_amount: u64,
+    pub start_at: i64,
+    pub cliff_at: i64,
+    pub end_at: i64,
+    /// Whoever paid the rent for the vesting and its vault when the offer was taken
+    pub rent_payer: Pubkey,
+    pub bump: u8,
+}
+
+impl Vesting {
+    /// Amount unlocked at `now`: nothing before the cliff, then linear from
+    /// `start_at` until everything is unlocked at `end_at`.
+    /// Rounds down, so the beneficiary never gets ahead of the schedule.
+    pub fn unlocked_amount(&self, now: i64) -> Option<u64> {
+        if now < self.cliff_at {
+            return Some(0);
+        }
+        if now >= self.end_at {
+            return Some(self.total_amount);
+        }
+
+        checked_mul_div(
+            self.total_amount,
+            (now - self.start_at) as u64,
+            (self.end_at - self.start_at) as u64,
+            Rounding::Down,
+        )
+    }
+}
@@ -1,9 +1,22 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        program_option::COption,
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
 
-use anchor_spl::token_interface::{
-    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+use anchor_spl::{
+    metadata::{mpl_token_metadata::accounts::MasterEdition, MetadataAccount},
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
 };
 
+use crate::{error::ErrorCode, state::BasketLeg};
+
 pub fn transfer_tokens<'info>(
     from: &InterfaceAccount<'info, TokenAccount>,
     to: &InterfaceAccount<'info, TokenAccount>,
@@ -23,3 +36,260 @@ pub fn transfer_tokens<'info>(
 
     transfer_checked(cpi_context, *amount, mint.decimals)
 }
+
+/// Same as `transfer_tokens`, but the authority is a PDA (usually the `offer`)
+/// which signs through `signer_seeds`.
+pub fn transfer_tokens_with_signer<'info>(
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    amount: &u64,
+    mint: &InterfaceAccount<'info, Mint>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let transfer_checked_options = TransferChecked {
+        from: from.to_account_info(),
+        to: to.to_account_info(),
+        mint: mint.to_account_info(),
+        authority: authority.clone(),
+    };
+
+    let cpi_context = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        transfer_checked_options,
+        signer_seeds,
+    );
+
+    transfer_checked(cpi_context, *amount, mint.decimals)
+}
+
+/// Closes a token account owned by a PDA, sending its rent to `destination`.
+pub fn close_token_account<'info>(
+    account: &InterfaceAccount<'info, TokenAccount>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<()> {
+    let accounts = CloseAccount {
+        account: account.to_account_info(),
+        destination: destination.clone(),
+        authority: authority.clone(),
+    };
+
+    let cpi_context =
+        CpiContext::new_with_signer(token_program.to_account_info(), accounts, signer_seeds);
+
+    close_account(cpi_context)
+}
+
+/// Checks that an earlier instruction of this transaction made `offer`, so its terms
+/// are only ever set before anyone can see, let alone take, the offer.
+///
+/// An instruction of this program making an offer counts when `offer` is the offer
+/// it makes, at that account's index in its `Accounts` struct. They all create it
+/// with `init`, so they only succeed for an offer that didn't exist yet; an offer
+/// listed anywhere else (e.g. as a remaining account) doesn't count.
+pub fn check_offer_made_in_this_transaction(
+    offer: &Pubkey,
+    instructions: &AccountInfo,
+) -> Result<()> {
+    // The discriminator of each instruction making an offer, and the index of the
+    // offer it makes among its accounts
+    const OFFER_MAKERS: [(&[u8], usize); 5] = [
+        (crate::instruction::MakeOffer::DISCRIMINATOR, 5),
+        (crate::instruction::MakeApprovalOffer::DISCRIMINATOR, 6),
+        (crate::instruction::MakeNftOffer::DISCRIMINATOR, 6),
+        (crate::instruction::MakeCollectionBid::DISCRIMINATOR, 4),
+        (crate::instruction::ReplaceOffer::DISCRIMINATOR, 10),
+    ];
+
+    let current_index = load_current_index_checked(instructions)?;
+    for index in 0..current_index {
+        let instruction = load_instruction_at_checked(index as usize, instructions)?;
+        if instruction.program_id != crate::ID {
+            continue;
+        }
+
+        let made_offer = OFFER_MAKERS
+            .iter()
+            .find(|(discriminator, _)| instruction.data.starts_with(discriminator))
+            .and_then(|(_, offer_index)| instruction.accounts.get(*offer_index));
+
+        if made_offer.is_some_and(|meta| meta.pubkey == *offer) {
+            return Ok(());
+        }
+    }
+
+    Err(ErrorCode::OfferTermsLocked.into())
+}
+
+/// Checks that the instruction right before the current one is an Ed25519 program
+/// instruction verifying `signer`'s signature over `message`.
+///
+/// The Ed25519 program fails the whole transaction if the signature is invalid, so
+/// it's enough to check that it was asked to verify the expected key and message.
+pub fn verify_ed25519_instruction(
+    instructions: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    // Layout of the Ed25519 instruction data: number of signatures (u8), padding (u8)
+    // and then, per signature, seven u16 offsets
+    const OFFSETS_START: usize = 2;
+    const OFFSETS_SIZE: usize = 14;
+    const PUBKEY_SIZE: usize = 32;
+
+    let current_index = load_current_index_checked(instructions)?;
+    if current_index == 0 {
+        return Err(ErrorCode::MissingEd25519Instruction.into());
+    }
+
+    let instruction = load_instruction_at_checked((current_index - 1) as usize, instructions)?;
+    if instruction.program_id != ed25519_program::ID {
+        return Err(ErrorCode::MissingEd25519Instruction.into());
+    }
+
+    let data = &instruction.data;
+    if data.len() < OFFSETS_START + OFFSETS_SIZE || data[0] != 1 {
+        return Err(ErrorCode::InvalidEd25519Instruction.into());
+    }
+
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+
+    // Signature, public key and message must all live in the Ed25519 instruction itself
+    // (`u16::MAX`), otherwise they could point at data we never look at
+    let signature_instruction_index = read_u16(OFFSETS_START + 2);
+    let public_key_offset = read_u16(OFFSETS_START + 4) as usize;
+    let public_key_instruction_index = read_u16(OFFSETS_START + 6);
+    let message_data_offset = read_u16(OFFSETS_START + 8) as usize;
+    let message_data_size = read_u16(OFFSETS_START + 10) as usize;
+    let message_instruction_index = read_u16(OFFSETS_START + 12);
+
+    if signature_instruction_index != u16::MAX
+        || public_key_instruction_index != u16::MAX
+        || message_instruction_index != u16::MAX
+    {
+        return Err(ErrorCode::InvalidEd25519Instruction.into());
+    }
+
+    let signed_public_key = data
+        .get(public_key_offset..public_key_offset + PUBKEY_SIZE)
+        .ok_or(ErrorCode::InvalidEd25519Instruction)?;
+    let signed_message = data
+        .get(message_data_offset..message_data_offset + message_data_size)
+        .ok_or(ErrorCode::InvalidEd25519Instruction)?;
+
+    if signed_public_key != signer.as_ref() || signed_message != message {
+        return Err(ErrorCode::OrderSignatureMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Checks that `mint` is an NFT: a supply of exactly 1, 0 decimals, and no one able
+/// to mint more. The mint authority must be revoked, or be the mint's Token Metadata
+/// master edition, which holds it for every Metaplex NFT and never mints again.
+pub fn check_nft_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    if mint.supply != 1 || mint.decimals != 0 {
+        return Err(ErrorCode::NotAnNft.into());
+    }
+    if let COption::Some(mint_authority) = mint.mint_authority {
+        let (master_edition, _) = MasterEdition::find_pda(&mint.key());
+        if mint_authority != master_edition {
+            return Err(ErrorCode::NotAnNft.into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks that `metadata` is the token metadata of `mint` and that the NFT is a
+/// verified member of `collection`.
+pub fn check_verified_collection(
+    metadata: Option<&Account<MetadataAccount>>,
+    mint: &Pubkey,
+    collection: &Pubkey,
+) -> Result<()> {
+    let metadata = metadata.ok_or(ErrorCode::MissingNftMetadata)?;
+
+    // `Account` already checked the owner is the token metadata program
+    if metadata.mint != *mint {
+        return Err(ErrorCode::InvalidNftMetadata.into());
+    }
+
+    match &metadata.collection {
+        Some(nft_collection) if nft_collection.verified && nft_collection.key == *collection => {
+            Ok(())
+        }
+        _ => Err(ErrorCode::NftNotInCollection.into()),
+    }
+}
+
+/// The accounts moving one basket leg: its mint, where the tokens come from and
+/// where they go.
+pub struct BasketLegAccounts<'info> {
+    pub mint: InterfaceAccount<'info, Mint>,
+    pub from: InterfaceAccount<'info, TokenAccount>,
+    pub to: InterfaceAccount<'info, TokenAccount>,
+}
+
+/// Loads the `[mint, from, to]` accounts of `leg` from `remaining_accounts`, checking
+/// that the mint is the leg's and that both token accounts hold it.
+pub fn load_basket_leg<'info>(
+    accounts: &'info [AccountInfo<'info>],
+    leg: &BasketLeg,
+) -> Result<BasketLegAccounts<'info>> {
+    let [mint, from, to] = accounts else {
+        return Err(ErrorCode::InvalidBasketAccounts.into());
+    };
+
+    let leg_accounts = BasketLegAccounts {
+        mint: InterfaceAccount::try_from(mint)?,
+        from: InterfaceAccount::try_from(from)?,
+        to: InterfaceAccount::try_from(to)?,
+    };
+
+    if leg_accounts.mint.key() != leg.mint
+        || leg_accounts.from.mint != leg.mint
+        || leg_accounts.to.mint != leg.mint
+    {
+        return Err(ErrorCode::InvalidBasketAccounts.into());
+    }
+
+    Ok(leg_accounts)
+}
+
+/// Picks the token account the caller provided: either the associated token account,
+/// which can be created on the fly, or any other token account already checked
+/// through `token::` constraints.
+pub fn either_token_account<'a, 'info>(
+    associated: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+    other: Option<&'a InterfaceAccount<'info, TokenAccount>>,
+) -> Result<&'a InterfaceAccount<'info, TokenAccount>> {
+    match (associated, other) {
+        (Some(account), None) | (None, Some(account)) => Ok(account),
+        _ => Err(ErrorCode::InvalidTokenAccounts.into()),
+    }
+}
+
+/// Checks the maker's token account lets the `delegate` PDA pull `amount`. Run when
+/// a non-custodial offer is made and again when it's filled, since the maker can
+/// revoke or lower the allowance at any time; takers get a clear error instead of a
+/// token program one.
+pub fn check_delegate_allowance(
+    maker_token_account_a: &InterfaceAccount<TokenAccount>,
+    delegate: &Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if maker_token_account_a.delegate != COption::Some(*delegate) {
+        return Err(ErrorCode::DelegateNotApproved.into());
+    }
+    if maker_token_account_a.delegated_amount < amount {
+        return Err(ErrorCode::InsufficientAllowance.into());
+    }
+    if maker_token_account_a.amount < amount {
+        return Err(ErrorCode::InsufficientMakerBalance.into());
+    }
+    Ok(())
+}
@@ -0,0 +1,278 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::solana_program::sysvar,
+    solana_sdk::{
+        self,
+        commitment_config::CommitmentConfig,
+        signature::{Keypair, Signer},
+    },
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, take_offer_accounts, SetupStruct};
+
+use escrow_app::{self, ArbitrationTerms, DisputeOutcome};
+
+#[tokio::test]
+pub async fn take_arbitrated_offer_and_resolve_dispute() {
+    println!("\n//// resolve_dispute instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let maker_ata_b = get_associated_token_address(&maker_pubkey, &token_mint_b);
+
+    let (arbitration_pda, _) =
+        Pubkey::find_program_address(&[b"arbitration", offer_pda.as_ref()], &program_id);
+    let arbitration_vault = get_associated_token_address(&arbitration_pda, &token_mint_b);
+    let arbitration_vault_a = get_associated_token_address(&arbitration_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+
+    let arbiter = Keypair::new();
+    let arbiter_pubkey = arbiter.pubkey();
+    let arbiter_ata_b = get_associated_token_address(&arbiter_pubkey, &token_mint_b);
+
+    let taker_ata_account_b = rpc_client.get_account(&taker_ata_b).await.unwrap();
+    let taker_balance_b = TokenAccount::unpack(&taker_ata_account_b.data)
+        .unwrap()
+        .amount;
+    let maker_ata_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_balance_a = TokenAccount::unpack(&maker_ata_account_a.data)
+        .unwrap()
+        .amount;
+
+    ///////////// Make Offer + Arbitration (one transaction) /////////////
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    program
+        .request()
+        .instruction(make_offer_ix)
+        .accounts(escrow_app::accounts::SetOfferArbitration {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferArbitration {
+            arbitration: Some(ArbitrationTerms {
+                arbiter: arbiter_pubkey,
+                fee_bps: 1_000,
+                timeout_seconds: 3_600,
+                default_outcome: DisputeOutcome::Release,
+            }),
+        })
+        .send()
+        .await
+        .unwrap();
+
+    // Once the offer is live, the arbitration terms can't change under the taker
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::SetOfferArbitration {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferArbitration { arbitration: None })
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Arbitration shouldn't be dropped from a live offer"
+    );
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            arbitration: Some(arbitration_pda),
+            arbitration_vault: Some(arbitration_vault),
+            arbitration_vault_a: Some(arbitration_vault_a),
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert both sides are held: the payment isn't paid to the maker, and token A
+    // isn't delivered to the taker
+    let arbitration_vault_account = rpc_client.get_account(&arbitration_vault).await.unwrap();
+    let arbitration_vault_data = TokenAccount::unpack(&arbitration_vault_account.data).unwrap();
+    assert_eq!(arbitration_vault_data.amount, token_b_amount_wanted);
+
+    let arbitration_vault_account_a = rpc_client.get_account(&arbitration_vault_a).await.unwrap();
+    let arbitration_vault_data_a = TokenAccount::unpack(&arbitration_vault_account_a.data).unwrap();
+    assert_eq!(arbitration_vault_data_a.amount, token_a_offered_amount);
+
+    ///////////// Raise Dispute /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::RaiseDispute {
+            authority: taker_pubkey,
+            arbitration: arbitration_pda,
+        })
+        .args(escrow_app::instruction::RaiseDispute {})
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("RaiseDispute Successful with signature: {}", signature);
+
+    ///////////// Resolve Dispute /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::SettleArbitration {
+            authority: arbiter_pubkey,
+            payer: maker_pubkey,
+            taker: taker_pubkey,
+            maker: maker_pubkey,
+            payout: maker_pubkey,
+            recipient: taker_pubkey,
+            arbiter: arbiter_pubkey,
+            rent_payer: taker_pubkey,
+            token_mint: token_mint_b,
+            token_mint_a,
+            payout_token_account: maker_ata_b,
+            taker_token_account: taker_ata_b,
+            arbiter_token_account: Some(arbiter_ata_b),
+            recipient_token_account_a: taker_ata_a,
+            maker_token_account_a: maker_ata_a,
+            arbitration: arbitration_pda,
+            arbitration_vault,
+            arbitration_vault_a,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::ResolveDispute {
+            maker_share_bps: 5_000,
+        })
+        .signer(arbiter.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("ResolveDispute Successful with signature: {}", signature);
+
+    // The arbiter takes 10%, the rest is split evenly
+    let fee = token_b_amount_wanted / 10;
+    let share = (token_b_amount_wanted - fee) / 2;
+
+    let arbiter_ata_account_b = rpc_client.get_account(&arbiter_ata_b).await.unwrap();
+    let arbiter_data_b = TokenAccount::unpack(&arbiter_ata_account_b.data).unwrap();
+    assert_eq!(arbiter_data_b.amount, fee);
+
+    let maker_ata_account_b = rpc_client.get_account(&maker_ata_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_ata_account_b.data).unwrap();
+    assert_eq!(maker_data_b.amount, share);
+
+    let taker_ata_account_b = rpc_client.get_account(&taker_ata_b).await.unwrap();
+    let taker_data_b = TokenAccount::unpack(&taker_ata_account_b.data).unwrap();
+    assert_eq!(
+        taker_data_b.amount,
+        taker_balance_b - token_b_amount_wanted + share
+    );
+
+    // Token A is split the same way, without a fee: half to the taker, half back to
+    // the maker
+    let share_a = token_a_offered_amount / 2;
+
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, share_a);
+
+    let maker_ata_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data_a = TokenAccount::unpack(&maker_ata_account_a.data).unwrap();
+    assert_eq!(
+        maker_data_a.amount,
+        maker_balance_a - token_a_offered_amount + share_a
+    );
+
+    // Asset arbitration PDA
+    let arbitration_closed = rpc_client.get_account(&arbitration_pda).await;
+    assert!(
+        arbitration_closed.is_err(),
+        "Arbitration account should be closed once resolved"
+    );
+
+    println!();
+}
@@ -0,0 +1,320 @@
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_client::{
+    anchor_lang::solana_program::{bpf_loader_upgradeable, sysvar},
+    solana_sdk::{
+        self,
+        commitment_config::CommitmentConfig,
+        instruction::AccountMeta,
+        signature::{read_keypair_file, Keypair, Signer},
+    },
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, take_offer_accounts, SetupStruct};
+
+#[tokio::test]
+pub async fn take_offer_with_attestation() {
+    println!("\n//// take_offer instruction (attestation-gated offer) ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+
+    ///////////// Registry + Attestation /////////////
+    // The program's upgrade authority is the registry admin
+    let admin = read_keypair_file(format!(
+        "{}/.config/solana/id.json",
+        std::env::var("HOME").unwrap()
+    ))
+    .unwrap();
+    let admin_pubkey = admin.pubkey();
+    let authority = Keypair::new();
+    let authority_pubkey = authority.pubkey();
+
+    let (registry_pda, _) = Pubkey::find_program_address(&[b"registry"], &program_id);
+    let (program_data, _) =
+        Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::ID);
+    let (attestation_pda, _) = Pubkey::find_program_address(
+        &[
+            b"attestation",
+            authority_pubkey.as_ref(),
+            taker_pubkey.as_ref(),
+        ],
+        &program_id,
+    );
+
+    // The registry is program-wide, it may already exist from a previous run
+    if rpc_client.get_account(&registry_pda).await.is_err() {
+        program
+            .request()
+            .accounts(escrow_app::accounts::InitRegistry {
+                admin: admin_pubkey,
+                payer: admin_pubkey,
+                registry: registry_pda,
+                program: program_id,
+                program_data,
+                system_program: solana_sdk::system_program::id(),
+            })
+            .args(escrow_app::instruction::InitRegistry {})
+            .signer(admin.insecure_clone())
+            .send()
+            .await
+            .unwrap();
+    }
+
+    program
+        .request()
+        .accounts(escrow_app::accounts::SetRegistryAuthorities {
+            admin: admin_pubkey,
+            registry: registry_pda,
+        })
+        .args(escrow_app::instruction::SetRegistryAuthorities {
+            authorities: vec![authority_pubkey],
+        })
+        .signer(admin.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    program
+        .request()
+        .accounts(escrow_app::accounts::IssueAttestation {
+            authority: authority_pubkey,
+            payer: maker_pubkey,
+            wallet: taker_pubkey,
+            registry: registry_pda,
+            attestation: attestation_pda,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::IssueAttestation {
+            tier: 2,
+            expires_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap()
+                .as_secs() as i64
+                + 3600,
+        })
+        .signer(authority.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Make Offer + Require Attestation (one transaction) /////////////
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    program
+        .request()
+        .instruction(make_offer_ix)
+        .accounts(escrow_app::accounts::SetOfferAttestation {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferAttestation {
+            min_attestation_tier: Some(1),
+        })
+        .send()
+        .await
+        .unwrap();
+
+    // Once the offer is live, the requirement can't change under the taker
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::SetOfferAttestation {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferAttestation {
+            min_attestation_tier: Some(3),
+        })
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Attestation requirement shouldn't change on a live offer"
+    );
+
+    // Nor by listing the live offer in a throwaway offer made in the same transaction
+    let throwaway_offer_id: u64 = 2;
+    let (throwaway_offer_pda, _) = Pubkey::find_program_address(
+        &[
+            b"offer",
+            maker_pubkey.as_ref(),
+            &throwaway_offer_id.to_le_bytes(),
+        ],
+        &program_id,
+    );
+
+    let throwaway_make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: throwaway_offer_pda,
+            vault: get_associated_token_address(&throwaway_offer_pda, &token_mint_a),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .accounts(vec![AccountMeta::new_readonly(offer_pda, false)])
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id: throwaway_offer_id,
+            token_a_offered_amount: 1,
+            token_b_amount_wanted: 1,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    let result = program
+        .request()
+        .instruction(throwaway_make_offer_ix)
+        .accounts(escrow_app::accounts::SetOfferAttestation {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferAttestation {
+            min_attestation_tier: Some(3),
+        })
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "A throwaway offer listing the live one shouldn't unlock its terms"
+    );
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            registry: Some(registry_pda),
+            attestation: Some(attestation_pda),
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert taker's token's balance of token mint a
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    ///////////// Revoke Attestation /////////////
+    let maker_balance_before = rpc_client.get_balance(&maker_pubkey).await.unwrap();
+
+    program
+        .request()
+        .accounts(escrow_app::accounts::RevokeAttestation {
+            authority: authority_pubkey,
+            rent_payer: maker_pubkey,
+            attestation: attestation_pda,
+        })
+        .args(escrow_app::instruction::RevokeAttestation {})
+        .signer(authority.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    // Assert the attestation is closed and its rent, more than the fee, went back to
+    // whoever paid it
+    let attestation_closed = rpc_client.get_account(&attestation_pda).await;
+    assert!(
+        attestation_closed.is_err(),
+        "Attestation account should be closed"
+    );
+    let maker_balance_after = rpc_client.get_balance(&maker_pubkey).await.unwrap();
+    assert!(maker_balance_after > maker_balance_before);
+
+    println!();
+}
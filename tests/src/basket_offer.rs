@@ -0,0 +1,279 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{
+        self, commitment_config::CommitmentConfig, instruction::AccountMeta, signature::Signer,
+    },
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{
+    create_token_with_balance, get_or_create_ata, initialize, simulate_quote, take_offer_accounts,
+    SetupStruct,
+};
+
+use escrow_app::{
+    self,
+    error::ErrorCode,
+    state::{BasketLeg, Offer},
+};
+
+#[tokio::test]
+pub async fn make_and_take_basket_offer() {
+    println!("\n//// add_offered_leg / add_wanted_leg instructions ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Token C is offered on top of Token A, Token D is wanted on top of Token B
+    let token_c_amount: u64 = 50;
+    let token_d_amount: u64 = 30;
+    let (token_mint_c, maker_ata_c) =
+        create_token_with_balance(&rpc_client, &maker, 0, token_c_amount).await;
+    let (token_mint_d, taker_ata_d) =
+        create_token_with_balance(&rpc_client, &taker, 0, token_d_amount).await;
+
+    let taker_ata_c = get_or_create_ata(&rpc_client, &taker, &token_mint_c).await;
+    let maker_ata_d = get_or_create_ata(&rpc_client, &maker, &token_mint_d).await;
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+    let leg_vault_c = get_associated_token_address(&offer_pda, &token_mint_c);
+
+    ///////////// Make Offer + Add Legs (one transaction) /////////////
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    let add_offered_leg_ix = program
+        .request()
+        .accounts(escrow_app::accounts::AddOfferedLeg {
+            owner: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            mint: token_mint_c,
+            owner_token_account: maker_ata_c,
+            offer: offer_pda,
+            leg_vault: leg_vault_c,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::AddOfferedLeg {
+            amount: token_c_amount,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    let add_wanted_leg_ix = program
+        .request()
+        .accounts(escrow_app::accounts::AddWantedLeg {
+            owner: maker_pubkey,
+            token_mint_a,
+            mint: token_mint_d,
+            offer: offer_pda,
+            token_program: spl_token::id(),
+        })
+        .args(escrow_app::instruction::AddWantedLeg {
+            amount: token_d_amount,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    let signature = program
+        .request()
+        .instruction(make_offer_ix)
+        .instruction(add_offered_leg_ix)
+        .instruction(add_wanted_leg_ix)
+        .send()
+        .await
+        .unwrap();
+
+    println!("Basket offer made with signature: {}", signature);
+
+    // Asset Offer info
+    let offer_account = rpc_client.get_account(&offer_pda).await.unwrap();
+    let offer = Offer::try_deserialize(&mut offer_account.data.as_slice()).unwrap();
+
+    assert_eq!(
+        offer.basket_offered,
+        vec![BasketLeg {
+            mint: token_mint_c,
+            amount: token_c_amount
+        }]
+    );
+    assert_eq!(
+        offer.basket_wanted,
+        vec![BasketLeg {
+            mint: token_mint_d,
+            amount: token_d_amount
+        }]
+    );
+
+    // Wanted legs first, then offered legs: [mint, from, to] for each
+    let basket_accounts = vec![
+        AccountMeta::new_readonly(token_mint_d, false),
+        AccountMeta::new(taker_ata_d, false),
+        AccountMeta::new(maker_ata_d, false),
+        AccountMeta::new_readonly(token_mint_c, false),
+        AccountMeta::new(leg_vault_c, false),
+        AccountMeta::new(taker_ata_c, false),
+    ];
+
+    let quote_take_ix = |basket_accounts: Vec<AccountMeta>| {
+        program
+            .request()
+            .accounts(escrow_app::accounts::QuoteTake {
+                taker: taker_pubkey,
+                recipient: taker_pubkey,
+                payout: maker_pubkey,
+                rent_payer: maker_pubkey,
+                maker: maker_pubkey,
+                token_mint_a,
+                token_mint_b,
+                token_b_metadata: None,
+                taker_token_account_b: None,
+                offer: offer_pda,
+                maker_token_account_a: None,
+                delegate: None,
+                registry: None,
+                attestation: None,
+                gate_token_account: None,
+                token_program: spl_token::id(),
+            })
+            .accounts(basket_accounts)
+            .args(escrow_app::instruction::QuoteTake {
+                preimage: None,
+                token_a_amount: None,
+            })
+            .instructions()
+            .unwrap()
+            .remove(0)
+    };
+
+    ///////////// Quote the take /////////////
+    let quote = simulate_quote(&rpc_client, quote_take_ix(basket_accounts.clone()), &taker).await;
+
+    assert_eq!(quote.error, None);
+    assert_eq!(quote.token_a_amount, token_a_offered_amount);
+    assert_eq!(
+        quote.basket_wanted,
+        vec![BasketLeg {
+            mint: token_mint_d,
+            amount: token_d_amount
+        }]
+    );
+    assert_eq!(
+        quote.basket_offered,
+        vec![BasketLeg {
+            mint: token_mint_c,
+            amount: token_c_amount
+        }]
+    );
+
+    // Without the basket accounts, the take would fail
+    let quote = simulate_quote(&rpc_client, quote_take_ix(vec![]), &taker).await;
+    assert_eq!(quote.error, Some(ErrorCode::InvalidBasketAccounts.into()));
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            token_mint_a,
+            token_mint_b,
+        ))
+        .accounts(basket_accounts)
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the taker got Token C
+    let taker_ata_account_c = rpc_client.get_account(&taker_ata_c).await.unwrap();
+    let taker_data_c = TokenAccount::unpack(&taker_ata_account_c.data).unwrap();
+    assert_eq!(taker_data_c.amount, token_c_amount);
+
+    // Assert the maker got Token D
+    let maker_ata_account_d = rpc_client.get_account(&maker_ata_d).await.unwrap();
+    let maker_data_d = TokenAccount::unpack(&maker_ata_account_d.data).unwrap();
+    assert_eq!(maker_data_d.amount, token_d_amount);
+
+    // Asset leg vault
+    let leg_vault_closed = rpc_client.get_account(&leg_vault_c).await;
+    assert!(
+        leg_vault_closed.is_err(),
+        "Leg vault should be closed after take"
+    );
+
+    println!();
+}
@@ -1,7 +1,16 @@
-use std::str::FromStr;
+use std::{
+    str::FromStr,
+    thread::sleep,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use anchor_client::{
-    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    solana_sdk::{
+        self,
+        commitment_config::CommitmentConfig,
+        native_token::sol_to_lamports,
+        signature::{Keypair, Signer},
+    },
     Cluster,
 };
 use spl_associated_token_account::get_associated_token_address;
@@ -13,8 +22,6 @@ use spl_token::{
 
 use crate::utils::{initialize, SetupStruct};
 
-use escrow_app;
-
 #[tokio::test]
 pub async fn make_and_cancel_offer() {
     println!("\n//// cancel_offer instruction ////");
@@ -60,6 +67,7 @@ pub async fn make_and_cancel_offer() {
         .request()
         .accounts(escrow_app::accounts::MakeOffer {
             maker: maker_pubkey,
+            payer: maker_pubkey,
             token_mint_a,
             token_mint_b,
             maker_token_account_a: maker_ata_a,
@@ -73,6 +81,8 @@ pub async fn make_and_cancel_offer() {
             offer_id,
             token_a_offered_amount,
             token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
         })
         .send()
         .await
@@ -84,11 +94,16 @@ pub async fn make_and_cancel_offer() {
     let signature = program
         .request()
         .accounts(escrow_app::accounts::CancelOffer {
+            authority: maker_pubkey,
+            payer: maker_pubkey,
             maker: maker_pubkey,
+            owner: maker_pubkey,
+            rent_payer: maker_pubkey,
             token_mint_a,
-            maker_token_account_a: maker_ata_a,
+            owner_token_account_a: Some(maker_ata_a),
+            other_owner_token_account_a: None,
             offer: offer_pda,
-            vault: vault_ata,
+            vault: Some(vault_ata),
             token_program: spl_token::id(),
             associated_token_program: spl_associated_token_account::ID,
             system_program: solana_sdk::system_program::id(),
@@ -125,3 +140,280 @@ pub async fn make_and_cancel_offer() {
 
     println!();
 }
+
+#[tokio::test]
+pub async fn make_and_cancel_sponsored_offer() {
+    println!("\n//// cancel_offer instruction (sponsored by a relayer) ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker: _,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    // The relayer pays the fees and the rent, the maker only signs
+    let relayer = Keypair::new();
+    rpc_client
+        .request_airdrop(&relayer.pubkey(), sol_to_lamports(5.0))
+        .await
+        .unwrap();
+    sleep(Duration::from_secs(1));
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &relayer,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let relayer_pubkey = relayer.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let maker_account_balance_before = rpc_client.get_balance(&maker_pubkey).await.unwrap();
+
+    // Send transaction via Anchor client (Make Offer)
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: relayer_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .signer(maker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Cancel Offer /////////////
+    let relayer_account_balance_before = rpc_client.get_balance(&relayer_pubkey).await.unwrap();
+
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::CancelOffer {
+            authority: maker_pubkey,
+            payer: relayer_pubkey,
+            maker: maker_pubkey,
+            owner: maker_pubkey,
+            rent_payer: relayer_pubkey,
+            token_mint_a,
+            owner_token_account_a: Some(maker_ata_a),
+            other_owner_token_account_a: None,
+            offer: offer_pda,
+            vault: Some(vault_ata),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::CancelOffer {})
+        .signer(maker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!(
+        "Sponsored CancelOffer Successful with signature: {}",
+        signature
+    );
+
+    // Assert the maker didn't spend any SOL
+    let maker_account_balance_after = rpc_client.get_balance(&maker_pubkey).await.unwrap();
+    assert_eq!(maker_account_balance_after, maker_account_balance_before);
+
+    // Assert the rent went back to the relayer
+    let relayer_account_balance_after = rpc_client.get_balance(&relayer_pubkey).await.unwrap();
+    assert!(relayer_account_balance_after > relayer_account_balance_before);
+
+    // Assert maker's token's balance of token mint a
+    let maker_ata_account = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data = TokenAccount::unpack(&maker_ata_account.data).unwrap();
+    assert_eq!(maker_data.amount, token_a_offered_amount);
+
+    println!();
+}
+
+#[tokio::test]
+pub async fn cancel_committed_offer() {
+    println!("\n//// set_offer_commitment instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker: _,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    ///////////// Make Offer + Commitment (one transaction) /////////////
+    let min_live_until = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+        + 2;
+
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    program
+        .request()
+        .instruction(make_offer_ix)
+        .accounts(escrow_app::accounts::SetOfferCommitment {
+            owner: maker_pubkey,
+            offer: offer_pda,
+        })
+        .args(escrow_app::instruction::SetOfferCommitment { min_live_until })
+        .send()
+        .await
+        .unwrap();
+
+    // Assert the commitment shows in the offer's data
+    let offer: escrow_app::state::Offer = program.account(offer_pda).await.unwrap();
+    assert_eq!(offer.min_live_until, Some(min_live_until));
+
+    ///////////// Cancel Offer /////////////
+    let cancel_offer_accounts = || escrow_app::accounts::CancelOffer {
+        authority: maker_pubkey,
+        payer: maker_pubkey,
+        maker: maker_pubkey,
+        owner: maker_pubkey,
+        rent_payer: maker_pubkey,
+        token_mint_a,
+        owner_token_account_a: Some(maker_ata_a),
+        other_owner_token_account_a: None,
+        offer: offer_pda,
+        vault: Some(vault_ata),
+        token_program: spl_token::id(),
+        associated_token_program: spl_associated_token_account::ID,
+        system_program: solana_sdk::system_program::id(),
+    };
+
+    let result = program
+        .request()
+        .accounts(cancel_offer_accounts())
+        .args(escrow_app::instruction::CancelOffer {})
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Committed offer shouldn't be cancellable before min_live_until"
+    );
+
+    // Wait for the commitment to end
+    sleep(Duration::from_secs(4));
+
+    let signature = program
+        .request()
+        .accounts(cancel_offer_accounts())
+        .args(escrow_app::instruction::CancelOffer {})
+        .send()
+        .await
+        .unwrap();
+
+    println!("CancelOffer Successful with signature: {}", signature);
+
+    // Assert maker's token's balance of token mint a
+    let maker_ata_account = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data = TokenAccount::unpack(&maker_ata_account.data).unwrap();
+    assert_eq!(maker_data.amount, token_a_offered_amount);
+
+    // Asset offer PDA
+    let offer_closed = rpc_client.get_account(&offer_pda).await;
+    assert!(
+        offer_closed.is_err(),
+        "Offer account should be closed after cancel"
+    );
+
+    // Asset vault PDA
+    let vault_closed = rpc_client.get_account(&vault_ata).await;
+    assert!(
+        vault_closed.is_err(),
+        "Vault ATA should be closed after cancel"
+    );
+
+    println!();
+}
@@ -0,0 +1,179 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, take_offer_accounts, SetupStruct};
+
+use escrow_app::{self, state::Claim};
+
+#[tokio::test]
+pub async fn take_offer_and_claim_proceeds() {
+    println!("\n//// claim_proceeds instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let maker_ata_b = get_associated_token_address(&maker_pubkey, &token_mint_b);
+
+    // The first take of the offer, so the claim is seeded with a fill index of 0
+    let (claim_pda, _) = Pubkey::find_program_address(
+        &[b"claim", offer_pda.as_ref(), &0u32.to_le_bytes()],
+        &program_id,
+    );
+    let claim_vault = get_associated_token_address(&claim_pda, &token_mint_b);
+
+    // Send transaction via Anchor client (Make Offer)
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Take Offer (pull settlement) /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            claim: Some(claim_pda),
+            claim_vault: Some(claim_vault),
+            maker_token_account_b: None,
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the proceeds wait in the claim vault
+    let claim_vault_account = rpc_client.get_account(&claim_vault).await.unwrap();
+    let claim_vault_data = TokenAccount::unpack(&claim_vault_account.data).unwrap();
+    assert_eq!(claim_vault_data.amount, token_b_amount_wanted);
+
+    // Asset Claim info
+    let claim_account = rpc_client.get_account(&claim_pda).await.unwrap();
+    let claim = Claim::try_deserialize(&mut claim_account.data.as_slice()).unwrap();
+
+    assert_eq!(claim.offer, offer_pda);
+    assert_eq!(claim.fill_index, 0);
+    assert_eq!(claim.maker, maker_pubkey);
+    assert_eq!(claim.payout, maker_pubkey);
+    assert_eq!(claim.mint, token_mint_b);
+    assert_eq!(claim.rent_payer, taker_pubkey);
+
+    ///////////// Claim Proceeds /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::ClaimProceeds {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            payout: maker_pubkey,
+            rent_payer: taker_pubkey,
+            mint: token_mint_b,
+            payout_token_account: maker_ata_b,
+            claim: claim_pda,
+            claim_vault,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::ClaimProceeds {})
+        .send()
+        .await
+        .unwrap();
+
+    println!("ClaimProceeds Successful with signature: {}", signature);
+
+    // Assert maker's token's balance of token mint b
+    let maker_ata_account_b = rpc_client.get_account(&maker_ata_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_ata_account_b.data).unwrap();
+    assert_eq!(maker_data_b.amount, token_b_amount_wanted);
+
+    // Asset claim PDA and claim vault
+    let claim_closed = rpc_client.get_account(&claim_pda).await;
+    assert!(
+        claim_closed.is_err(),
+        "Claim account should be closed after claim"
+    );
+
+    let claim_vault_closed = rpc_client.get_account(&claim_vault).await;
+    assert!(
+        claim_vault_closed.is_err(),
+        "Claim vault should be closed after claim"
+    );
+
+    println!();
+}
@@ -0,0 +1,183 @@
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{
+        self, commitment_config::CommitmentConfig, signature::Signer, sysvar,
+        transaction::Transaction,
+    },
+    Cluster,
+};
+use solana_ed25519_program::new_ed25519_instruction_with_signature;
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, SetupStruct};
+
+use escrow_app::{
+    self,
+    state::{OrderNonce, SignedOrder},
+};
+
+#[tokio::test]
+pub async fn sign_and_fill_order() {
+    println!("\n//// fill_signed_order instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &taker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    let token_a_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (delegate_pda, _) =
+        Pubkey::find_program_address(&[b"delegate", maker_pubkey.as_ref()], &program_id);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+    let maker_ata_b = get_associated_token_address(&maker_pubkey, &token_mint_b);
+
+    // Maker approves the program's delegate PDA on their token A account
+    let approve_ix = spl_token::instruction::approve(
+        &spl_token::id(),
+        &maker_ata_a,
+        &delegate_pda,
+        &maker_pubkey,
+        &[],
+        token_a_amount,
+    )
+    .unwrap();
+
+    let recent_blockhash = rpc_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&maker_pubkey),
+        &[&maker],
+        recent_blockhash,
+    );
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .unwrap();
+
+    // Maker signs the order off-chain
+    let order = SignedOrder {
+        program_id,
+        maker: maker_pubkey,
+        token_mint_a,
+        token_mint_b,
+        token_a_amount,
+        token_b_amount,
+        expires_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64
+            + 3600,
+        nonce: 7,
+    };
+    let message = order.message().unwrap();
+    let signature = maker.sign_message(&message);
+    let ed25519_ix = new_ed25519_instruction_with_signature(
+        &message,
+        signature.as_ref().try_into().unwrap(),
+        &maker_pubkey.to_bytes(),
+    );
+
+    let (order_nonce_pda, _) = Pubkey::find_program_address(
+        &[b"nonce", maker_pubkey.as_ref(), &order.nonce.to_le_bytes()],
+        &program_id,
+    );
+
+    ///////////// Fill Signed Order /////////////
+    let signature = program
+        .request()
+        .instruction(ed25519_ix)
+        .accounts(escrow_app::accounts::FillSignedOrder {
+            taker: taker_pubkey,
+            payer: taker_pubkey,
+            maker: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            delegate: delegate_pda,
+            maker_token_account_a: maker_ata_a,
+            taker_token_account_a: taker_ata_a,
+            taker_token_account_b: taker_ata_b,
+            maker_token_account_b: maker_ata_b,
+            order_nonce: order_nonce_pda,
+            instructions: sysvar::instructions::id(),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::FillSignedOrder { order })
+        .send()
+        .await
+        .unwrap();
+
+    println!("FillSignedOrder Successful with signature: {}", signature);
+
+    // Assert maker's token's balance of token mint a
+    let maker_ata_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data_a = TokenAccount::unpack(&maker_ata_account_a.data).unwrap();
+    assert_eq!(maker_data_a.amount, 0);
+
+    // Assert maker's token's balance of token mint b
+    let maker_ata_account_b = rpc_client.get_account(&maker_ata_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_ata_account_b.data).unwrap();
+    assert_eq!(maker_data_b.amount, token_b_amount);
+
+    // Assert taker's token's balance of token mint a
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_amount);
+
+    // Assert the nonce is burnt
+    let order_nonce_account = rpc_client.get_account(&order_nonce_pda).await.unwrap();
+    let order_nonce =
+        OrderNonce::try_deserialize(&mut order_nonce_account.data.as_slice()).unwrap();
+    assert_eq!(order_nonce.maker, maker_pubkey);
+    assert_eq!(order_nonce.nonce, 7);
+    assert_eq!(order_nonce.rent_payer, taker_pubkey);
+
+    ///////////// Close Order Nonce /////////////
+    // The order hasn't expired yet, so its nonce must stay
+    let close_result = program
+        .request()
+        .accounts(escrow_app::accounts::CloseOrderNonce {
+            rent_payer: taker_pubkey,
+            order_nonce: order_nonce_pda,
+        })
+        .args(escrow_app::instruction::CloseOrderNonce {})
+        .send()
+        .await;
+    assert!(close_result.is_err());
+
+    println!();
+}
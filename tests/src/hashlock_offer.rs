@@ -0,0 +1,172 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    solana_sdk::{self, commitment_config::CommitmentConfig, hash::hash, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, take_offer_accounts, SetupStruct};
+
+#[tokio::test]
+pub async fn make_and_take_hash_locked_offer() {
+    println!("\n//// take_offer instruction (hash-locked offer) ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    // Secret shared off-chain with the counterparty
+    let preimage = [7u8; 32];
+    let hashlock = hash(&preimage).to_bytes();
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+    let maker_ata_b = get_associated_token_address(&maker_pubkey, &token_mint_b);
+
+    // Send transaction via Anchor client (Make Offer)
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: Some(hashlock),
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Take Offer /////////////
+    let maker_account_balance_before = rpc_client.get_balance(&maker_pubkey).await.unwrap();
+
+    // A wrong secret can't take the offer
+    let result = program
+        .request()
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            token_mint_a,
+            token_mint_b,
+        ))
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: Some([8u8; 32]),
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await;
+
+    assert!(result.is_err(), "Wrong preimage should be rejected");
+
+    let signature = program
+        .request()
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            token_mint_a,
+            token_mint_b,
+        ))
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: Some(preimage),
+            token_a_amount: None,
+        })
+        .signer(taker)
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Asset maker's account balance
+    let maker_account_balance_after = rpc_client.get_balance(&maker_pubkey).await.unwrap();
+    assert!(maker_account_balance_after > maker_account_balance_before);
+
+    // Assert maker's token's balance of token mint a
+    let maker_ata_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data_a = TokenAccount::unpack(&maker_ata_account_a.data).unwrap();
+    assert_eq!(maker_data_a.amount, 0);
+
+    // Assert maker's token's balance of token mint b
+    let maker_ata_account_b = rpc_client.get_account(&maker_ata_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_ata_account_b.data).unwrap();
+    assert_eq!(maker_data_b.amount, token_b_amount_wanted);
+
+    // Assert taker's token's balance of token mint a
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    // Assert taker's token's balance of token mint b
+    let taker_ata_account_b = rpc_client.get_account(&taker_ata_b).await.unwrap();
+    let taker_data_b = TokenAccount::unpack(&taker_ata_account_b.data).unwrap();
+    assert_eq!(taker_data_b.amount, 0);
+
+    // Asset offer PDA
+    let offer_closed = rpc_client.get_account(&offer_pda).await;
+    assert!(
+        offer_closed.is_err(),
+        "Offer account should be closed after take"
+    );
+
+    // Asset vault PDA
+    let vault_closed = rpc_client.get_account(&vault_ata).await;
+    assert!(
+        vault_closed.is_err(),
+        "Vault ATA should be closed after take"
+    );
+
+    println!();
+}
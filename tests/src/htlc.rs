@@ -0,0 +1,244 @@
+use std::{
+    str::FromStr,
+    thread::sleep,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use anchor_client::{
+    solana_sdk::{self, commitment_config::CommitmentConfig, hash::hash, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, SetupStruct};
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[tokio::test]
+pub async fn lock_and_claim_htlc() {
+    println!("\n//// lock_htlc / claim_htlc instructions ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_a_decimals,
+        maker_ata_a,
+        ..
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let htlc_id: u64 = 1;
+    let amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let preimage = [7u8; 32];
+    let hashlock = hash(&preimage).to_bytes();
+
+    let (htlc_pda, _) = Pubkey::find_program_address(
+        &[b"htlc", maker_pubkey.as_ref(), &htlc_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&htlc_pda, &token_mint_a);
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+
+    ///////////// Lock HTLC /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::LockHtlc {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint: token_mint_a,
+            maker_token_account: maker_ata_a,
+            htlc: htlc_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::LockHtlc {
+            htlc_id,
+            counterparty: taker_pubkey,
+            amount,
+            hashlock,
+            expires_at: now() + 3600,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    println!("LockHtlc Successful with signature: {}", signature);
+
+    // Assert vault balance == amount
+    let vault_acc = rpc_client.get_account(&vault_ata).await.unwrap();
+    let vault_data = TokenAccount::unpack(&vault_acc.data).unwrap();
+    assert_eq!(vault_data.amount, amount);
+
+    ///////////// Claim HTLC /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::ClaimHtlc {
+            payer: taker_pubkey,
+            maker: maker_pubkey,
+            counterparty: taker_pubkey,
+            rent_payer: maker_pubkey,
+            token_mint: token_mint_a,
+            counterparty_token_account: taker_ata_a,
+            htlc: htlc_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::ClaimHtlc { preimage })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("ClaimHtlc Successful with signature: {}", signature);
+
+    // Assert the counterparty got the tokens
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, amount);
+
+    // Asset HTLC PDA
+    let htlc_closed = rpc_client.get_account(&htlc_pda).await;
+    assert!(
+        htlc_closed.is_err(),
+        "HTLC account should be closed after claim"
+    );
+
+    println!();
+}
+
+#[tokio::test]
+pub async fn lock_and_refund_htlc() {
+    println!("\n//// lock_htlc / refund_htlc instructions ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_a_decimals,
+        maker_ata_a,
+        ..
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let htlc_id: u64 = 1;
+    let amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let hashlock = hash(&[7u8; 32]).to_bytes();
+
+    let (htlc_pda, _) = Pubkey::find_program_address(
+        &[b"htlc", maker_pubkey.as_ref(), &htlc_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&htlc_pda, &token_mint_a);
+
+    ///////////// Lock HTLC /////////////
+    program
+        .request()
+        .accounts(escrow_app::accounts::LockHtlc {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint: token_mint_a,
+            maker_token_account: maker_ata_a,
+            htlc: htlc_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::LockHtlc {
+            htlc_id,
+            counterparty: taker_pubkey,
+            amount,
+            hashlock,
+            expires_at: now() + 2,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    // Wait for the deadline to pass
+    sleep(Duration::from_secs(5));
+
+    ///////////// Refund HTLC /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::RefundHtlc {
+            payer: maker_pubkey,
+            maker: maker_pubkey,
+            rent_payer: maker_pubkey,
+            token_mint: token_mint_a,
+            maker_token_account: maker_ata_a,
+            htlc: htlc_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::RefundHtlc {})
+        .send()
+        .await
+        .unwrap();
+
+    println!("RefundHtlc Successful with signature: {}", signature);
+
+    // Assert the maker got the tokens back
+    let maker_ata_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data_a = TokenAccount::unpack(&maker_ata_account_a.data).unwrap();
+    assert_eq!(
+        maker_data_a.amount,
+        ui_amount_to_amount(100.0, token_mint_a_decimals)
+    );
+
+    // Asset HTLC PDA
+    let htlc_closed = rpc_client.get_account(&htlc_pda).await;
+    assert!(
+        htlc_closed.is_err(),
+        "HTLC account should be closed after refund"
+    );
+
+    println!();
+}
@@ -1,3 +1,4 @@
+#[cfg(test)]
 mod utils;
 
 #[cfg(test)]
@@ -9,8 +10,75 @@ mod cancel_offer;
 #[cfg(test)]
 mod take_offer;
 
+#[cfg(test)]
+mod replace_offer;
+
+#[cfg(test)]
+mod set_offer_manager;
+
+#[cfg(test)]
+mod fill_signed_order;
+
+#[cfg(test)]
+mod make_approval_offer;
+
+#[cfg(test)]
+mod make_nft_offer;
+
+#[cfg(test)]
+mod make_collection_bid;
+
+#[cfg(test)]
+mod basket_offer;
+
+#[cfg(test)]
+mod payment_option;
+
+#[cfg(test)]
+mod claim_proceeds;
+
+#[cfg(test)]
+mod hashlock_offer;
+
+#[cfg(test)]
+mod htlc;
+
+#[cfg(test)]
+mod attestation;
+
+#[cfg(test)]
+mod token_gate;
+
+#[cfg(test)]
+mod vesting;
+
+#[cfg(test)]
+mod arbitration;
+
+#[cfg(test)]
+mod milestone;
+
+#[cfg(test)]
+mod partial_fill;
+
+#[cfg(test)]
+mod math;
+
+#[cfg(test)]
+mod migrate_offer;
+
+#[cfg(test)]
+mod quote_take;
+
+#[cfg(test)]
+mod sweep;
+
+#[cfg(test)]
+mod transfer_offer_ownership;
+
 #[cfg(test)]
 #[test]
+#[allow(clippy::assertions_on_constants)]
 pub fn init() {
     assert!(1 == 3 - 2)
 }
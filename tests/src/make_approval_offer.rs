@@ -0,0 +1,206 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{
+        self, commitment_config::CommitmentConfig, signature::Signer, transaction::Transaction,
+    },
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, take_offer_accounts, SetupStruct};
+
+use escrow_app::{
+    self,
+    state::{Custody, Offer},
+};
+
+#[tokio::test]
+pub async fn make_and_take_approval_offer() {
+    println!("\n//// make_approval_offer instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+    let (delegate_pda, _) =
+        Pubkey::find_program_address(&[b"delegate", maker_pubkey.as_ref()], &program_id);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+    let maker_ata_b = get_associated_token_address(&maker_pubkey, &token_mint_b);
+
+    // Without an allowance the offer couldn't be filled, so it can't be made
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::MakeApprovalOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            delegate: delegate_pda,
+            offer: offer_pda,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeApprovalOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+        })
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Approval offer shouldn't be made before the delegate is approved"
+    );
+
+    // Maker approves the program's delegate PDA instead of depositing
+    let approve_ix = spl_token::instruction::approve(
+        &spl_token::id(),
+        &maker_ata_a,
+        &delegate_pda,
+        &maker_pubkey,
+        &[],
+        token_a_offered_amount,
+    )
+    .unwrap();
+
+    let recent_blockhash = rpc_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[approve_ix],
+        Some(&maker_pubkey),
+        &[&maker],
+        recent_blockhash,
+    );
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .unwrap();
+
+    ///////////// Make Approval Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::MakeApprovalOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            delegate: delegate_pda,
+            offer: offer_pda,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeApprovalOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    println!("MakeApprovalOffer Successful with signature: {}", signature);
+
+    // Asset Offer info
+    let offer_account = rpc_client.get_account(&offer_pda).await.unwrap();
+    let offer = Offer::try_deserialize(&mut offer_account.data.as_slice()).unwrap();
+
+    assert_eq!(offer.custody, Custody::Delegate);
+    assert_eq!(offer.token_a_offered_amount, token_a_offered_amount);
+
+    // Assert the tokens are still in the maker's account
+    let maker_ata_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data_a = TokenAccount::unpack(&maker_ata_account_a.data).unwrap();
+    assert_eq!(maker_data_a.amount, token_a_offered_amount);
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            maker_token_account_a: Some(maker_ata_a),
+            delegate: Some(delegate_pda),
+            vault: None,
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker)
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert maker's token's balance of token mint a
+    let maker_ata_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data_a = TokenAccount::unpack(&maker_ata_account_a.data).unwrap();
+    assert_eq!(maker_data_a.amount, 0);
+
+    // Assert maker's token's balance of token mint b
+    let maker_ata_account_b = rpc_client.get_account(&maker_ata_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_ata_account_b.data).unwrap();
+    assert_eq!(maker_data_b.amount, token_b_amount_wanted);
+
+    // Assert taker's token's balance of token mint a
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    // Asset offer PDA
+    let offer_closed = rpc_client.get_account(&offer_pda).await;
+    assert!(
+        offer_closed.is_err(),
+        "Offer account should be closed after take"
+    );
+
+    println!();
+}
@@ -0,0 +1,189 @@
+use std::{str::FromStr, thread::sleep, time::Duration};
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{
+        self, commitment_config::CommitmentConfig, native_token::sol_to_lamports,
+        signature::Keypair, signature::Signer,
+    },
+    Cluster,
+};
+use mpl_token_metadata::accounts::Metadata;
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{
+    create_metaplex_nft, initialize, take_offer_accounts, verify_collection, SetupStruct,
+};
+
+use escrow_app::{self, state::Offer};
+
+#[tokio::test]
+pub async fn make_and_take_collection_bid() {
+    println!("\n//// make_collection_bid instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b: _,
+        token_mint_a_decimals,
+        token_mint_b_decimals: _,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // A collection and one of its NFTs, held by the taker. The NFT isn't verified
+    // as a member yet.
+    let collection_authority = Keypair::new();
+    rpc_client
+        .request_airdrop(&collection_authority.pubkey(), sol_to_lamports(1.0))
+        .await
+        .unwrap();
+
+    sleep(Duration::from_secs(1));
+
+    let collection_mint = create_metaplex_nft(
+        &rpc_client,
+        &collection_authority,
+        &collection_authority.pubkey(),
+        None,
+    )
+    .await;
+    let nft_mint = create_metaplex_nft(
+        &rpc_client,
+        &collection_authority,
+        &taker_pubkey,
+        Some(collection_mint),
+    )
+    .await;
+    let (nft_metadata, _) = Metadata::find_pda(&nft_mint);
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+    let maker_nft_ata = get_associated_token_address(&maker_pubkey, &nft_mint);
+
+    ///////////// Make Collection Bid /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::MakeCollectionBid {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeCollectionBid {
+            offer_id,
+            token_a_offered_amount,
+            collection: collection_mint,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    println!("MakeCollectionBid Successful with signature: {}", signature);
+
+    // Asset Offer info
+    let offer_account = rpc_client.get_account(&offer_pda).await.unwrap();
+    let offer = Offer::try_deserialize(&mut offer_account.data.as_slice()).unwrap();
+
+    assert_eq!(offer.token_mint_b, Pubkey::default());
+    assert_eq!(offer.token_b_amount_wanted, 1);
+    assert_eq!(offer.token_b_collection, Some(collection_mint));
+
+    let take_accounts = || escrow_app::accounts::TakeOffer {
+        token_b_metadata: Some(nft_metadata),
+        ..take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            token_mint_a,
+            nft_mint,
+        )
+    };
+
+    ///////////// Take Offer with an unverified NFT /////////////
+    let result = program
+        .request()
+        .accounts(take_accounts())
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "NFT not verified as a member of the collection shouldn't be accepted"
+    );
+
+    verify_collection(
+        &rpc_client,
+        &collection_authority,
+        &nft_mint,
+        &collection_mint,
+    )
+    .await;
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(take_accounts())
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker)
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the maker got the NFT
+    let maker_nft_account = rpc_client.get_account(&maker_nft_ata).await.unwrap();
+    let maker_nft_data = TokenAccount::unpack(&maker_nft_account.data).unwrap();
+    assert_eq!(maker_nft_data.amount, 1);
+
+    // Assert taker's token's balance of token mint a
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    println!();
+}
@@ -0,0 +1,238 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::solana_program::sysvar,
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{
+    create_nft, create_token_with_balance, initialize, take_offer_accounts, SetupStruct,
+};
+
+use escrow_app::{self, state::Offer, FillPolicy};
+
+#[tokio::test]
+pub async fn make_and_take_nft_offer() {
+    println!("\n//// make_nft_offer instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a: _,
+        token_mint_b,
+        token_mint_a_decimals: _,
+        token_mint_b_decimals,
+        maker_ata_a: _,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // The maker sells an NFT instead of Token A
+    let (nft_mint, maker_nft_ata) = create_nft(&rpc_client, &maker).await;
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &nft_mint);
+
+    let taker_nft_ata = get_associated_token_address(&taker_pubkey, &nft_mint);
+    let maker_ata_b = get_associated_token_address(&maker_pubkey, &token_mint_b);
+
+    let make_nft_offer_ix = || {
+        program
+            .request()
+            .accounts(escrow_app::accounts::MakeNftOffer {
+                maker: maker_pubkey,
+                payer: maker_pubkey,
+                token_mint_a: nft_mint,
+                token_mint_b,
+                token_a_metadata: None,
+                maker_token_account_a: maker_nft_ata,
+                offer: offer_pda,
+                vault: vault_ata,
+                token_program: spl_token::id(),
+                associated_token_program: spl_associated_token_account::ID,
+                system_program: solana_sdk::system_program::id(),
+            })
+            .args(escrow_app::instruction::MakeNftOffer {
+                offer_id,
+                token_b_amount_wanted,
+                collection: None,
+            })
+            .instructions()
+            .unwrap()
+            .remove(0)
+    };
+
+    ///////////// Make NFT Offer + Fill Policy (one transaction) /////////////
+    // A single NFT can't be taken in parts, so the whole transaction fails
+    let result = program
+        .request()
+        .instruction(make_nft_offer_ix())
+        .accounts(escrow_app::accounts::SetOfferFillPolicy {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            token_mint_a: nft_mint,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferFillPolicy {
+            fill_policy: FillPolicy::Partial { min_fill_amount: 1 },
+        })
+        .send()
+        .await;
+
+    assert!(result.is_err(), "NFT offer shouldn't allow partial fills");
+
+    ///////////// Make NFT Offer /////////////
+    let signature = program
+        .request()
+        .instruction(make_nft_offer_ix())
+        .send()
+        .await
+        .unwrap();
+
+    println!("MakeNftOffer Successful with signature: {}", signature);
+
+    // Assert the NFT is in the vault
+    let vault_acc = rpc_client.get_account(&vault_ata).await.unwrap();
+    let vault_data = TokenAccount::unpack(&vault_acc.data).unwrap();
+    assert_eq!(vault_data.amount, 1);
+
+    // Asset Offer info
+    let offer_account = rpc_client.get_account(&offer_pda).await.unwrap();
+    let offer = Offer::try_deserialize(&mut offer_account.data.as_slice()).unwrap();
+
+    assert_eq!(offer.token_mint_a, nft_mint);
+    assert_eq!(offer.token_a_offered_amount, 1);
+    assert_eq!(offer.token_a_collection, None);
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            nft_mint,
+            token_mint_b,
+        ))
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker)
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the taker got the NFT
+    let taker_nft_account = rpc_client.get_account(&taker_nft_ata).await.unwrap();
+    let taker_nft_data = TokenAccount::unpack(&taker_nft_account.data).unwrap();
+    assert_eq!(taker_nft_data.amount, 1);
+
+    // Assert maker's token's balance of token mint b
+    let maker_ata_account_b = rpc_client.get_account(&maker_ata_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_ata_account_b.data).unwrap();
+    assert_eq!(maker_data_b.amount, token_b_amount_wanted);
+
+    println!();
+}
+
+#[tokio::test]
+pub async fn make_nft_offer_with_mintable_token() {
+    println!("\n//// make_nft_offer instruction with a mintable token ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker: _,
+        token_mint_a: _,
+        token_mint_b,
+        token_mint_a_decimals: _,
+        token_mint_b_decimals,
+        maker_ata_a: _,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+
+    // Supply of 1 and 0 decimals, but its authority can still mint more
+    let (token_mint, maker_token_ata) = create_token_with_balance(&rpc_client, &maker, 0, 1).await;
+
+    let offer_id: u64 = 1;
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::MakeNftOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a: token_mint,
+            token_mint_b,
+            token_a_metadata: None,
+            maker_token_account_a: maker_token_ata,
+            offer: offer_pda,
+            vault: get_associated_token_address(&offer_pda, &token_mint),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeNftOffer {
+            offer_id,
+            token_b_amount_wanted,
+            collection: None,
+        })
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Token whose authority can still mint shouldn't be sold as an NFT"
+    );
+
+    println!();
+}
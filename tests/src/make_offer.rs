@@ -61,6 +61,7 @@ async fn make_offer() {
         .request()
         .accounts(escrow_app::accounts::MakeOffer {
             maker: maker_pubkey,
+            payer: maker_pubkey,
             token_mint_a,
             token_mint_b,
             maker_token_account_a: maker_ata_a,
@@ -74,6 +75,8 @@ async fn make_offer() {
             offer_id,
             token_a_offered_amount,
             token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
         })
         .send()
         .await
@@ -103,6 +106,10 @@ async fn make_offer() {
     assert_eq!(offer.token_mint_a, token_mint_a);
     assert_eq!(offer.token_mint_b, token_mint_b);
     assert_eq!(offer.token_b_amount_wanted, token_b_amount_wanted);
+    assert_eq!(offer.manager, None);
+    assert_eq!(offer.rent_payer, maker_pubkey);
+    assert_eq!(offer.token_a_offered_amount, token_a_offered_amount);
+    assert_eq!(offer.payout, maker_pubkey);
 
     println!();
 }
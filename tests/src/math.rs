@@ -0,0 +1,103 @@
+use std::cmp::Ordering;
+
+use proptest::prelude::*;
+
+use escrow_app::math::{checked_mul_div, Rounding};
+use escrow_app::Price;
+
+/// Splits `total` into non-empty fills, sized by `cuts`.
+fn fills(total: u64, cuts: &[u64]) -> Vec<u64> {
+    let mut left = total;
+    let mut fills = Vec::new();
+
+    for cut in cuts {
+        if left == 0 {
+            break;
+        }
+        let fill = 1 + cut % left;
+        fills.push(fill);
+        left -= fill;
+    }
+
+    if left > 0 {
+        fills.push(left);
+    }
+    fills
+}
+
+proptest! {
+    #[test]
+    fn mul_div_rounds_as_asked(a: u64, b: u64, c in 1..=u64::MAX) {
+        let exact = a as u128 * b as u128;
+
+        if let Some(down) = checked_mul_div(a, b, c, Rounding::Down) {
+            prop_assert!(down as u128 * c as u128 <= exact);
+            prop_assert!((down as u128 + 1) * c as u128 > exact);
+        }
+
+        if let Some(up) = checked_mul_div(a, b, c, Rounding::Up) {
+            prop_assert!(up as u128 * c as u128 >= exact);
+            prop_assert!(up == 0 || (up as u128 - 1) * (c as u128) < exact);
+        }
+    }
+
+    #[test]
+    fn mul_div_fails_on_zero_divisor(a: u64, b: u64) {
+        prop_assert_eq!(checked_mul_div(a, b, 0, Rounding::Down), None);
+        prop_assert_eq!(checked_mul_div(a, b, 0, Rounding::Up), None);
+    }
+
+    #[test]
+    fn quote_never_pays_the_maker_less_than_the_ratio(
+        numerator: u64,
+        denominator in 1..=u64::MAX,
+        token_a_amount: u64,
+    ) {
+        let price = Price { numerator, denominator };
+
+        if let Some(quote) = price.quote(token_a_amount) {
+            prop_assert!(
+                quote as u128 * denominator as u128 >= numerator as u128 * token_a_amount as u128
+            );
+        }
+    }
+
+    #[test]
+    fn fills_add_up_to_the_quoted_price(
+        numerator: u64,
+        denominator in 1..=u64::MAX,
+        cuts in prop::collection::vec(any::<u64>(), 0..8),
+    ) {
+        let price = Price { numerator, denominator };
+        let mut filled = 0;
+        let mut paid: u64 = 0;
+
+        for fill in fills(denominator, &cuts) {
+            paid += price.quote_fill(filled, fill).unwrap();
+            filled += fill;
+
+            // At every point, the maker got at least their ratio of what was taken
+            prop_assert!(
+                paid as u128 * denominator as u128 >= numerator as u128 * filled as u128
+            );
+        }
+
+        // And once everything is taken, exactly the quoted price
+        prop_assert_eq!(paid, numerator);
+    }
+
+    #[test]
+    fn compare_orders_by_ratio(
+        numerator in 0..=u32::MAX as u64,
+        denominator in 1..=u32::MAX as u64,
+        scale in 1..=u32::MAX as u64,
+    ) {
+        let price = Price { numerator, denominator };
+        let same = Price { numerator: numerator * scale, denominator: denominator * scale };
+        let higher = Price { numerator: numerator + 1, denominator };
+
+        prop_assert_eq!(price.compare(&same), Ordering::Equal);
+        prop_assert_eq!(price.compare(&higher), Ordering::Less);
+        prop_assert_eq!(higher.compare(&price), Ordering::Greater);
+    }
+}
@@ -0,0 +1,168 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::{AccountDeserialize, AccountSerialize, Discriminator, Space},
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::solana_program::pubkey::Pubkey;
+
+use crate::utils::{initialize, SetupStruct};
+
+use escrow_app::{
+    self,
+    constants::{OFFER_RESERVED_BYTES, OFFER_VERSION},
+    state::{Custody, FillPolicy, Offer, OfferV1},
+};
+
+/// The maker of the old layout offer loaded into the test validator from
+/// `tests/fixtures/offer_v1.json` (see `Anchor.toml`).
+const FIXTURE_MAKER: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// The mint of the tokens offered by the fixture offer, loaded from
+/// `tests/fixtures/offer_v1_mint.json`. The fixture vault,
+/// `tests/fixtures/offer_v1_vault.json`, holds 100 of them.
+const FIXTURE_MINT_A: Pubkey = Pubkey::new_from_array([8; 32]);
+
+/// Builds the bytes of an old layout offer account field by field, the way they
+/// were laid out before `Offer` was versioned.
+fn offer_v1_account_data(maker: &Pubkey, id: u64, bump: u8) -> Vec<u8> {
+    let mut data = Offer::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(&id.to_le_bytes());
+    data.extend_from_slice(maker.as_ref()); // maker
+    data.extend_from_slice(FIXTURE_MINT_A.as_ref()); // token_mint_a
+    data.extend_from_slice(&[9; 32]); // token_mint_b
+    data.extend_from_slice(&80u64.to_le_bytes()); // token_b_amount_wanted
+    data.push(bump);
+    data
+}
+
+#[test]
+pub fn migrate_offer_v1_from_raw_bytes() {
+    let maker = Pubkey::new_unique();
+    let data = offer_v1_account_data(&maker, 1, 254);
+    assert_eq!(data.len(), OfferV1::ACCOUNT_SPACE);
+
+    let old_offer = OfferV1::try_from_account_data(&data).unwrap();
+    let offer = old_offer.into_offer(100);
+
+    assert_eq!(offer.version, OFFER_VERSION);
+    assert_eq!(offer.id, 1);
+    assert_eq!(offer.maker, maker);
+    assert_eq!(offer.token_mint_a, FIXTURE_MINT_A);
+    assert_eq!(offer.token_mint_b, Pubkey::new_from_array([9; 32]));
+    assert_eq!(offer.token_b_amount_wanted, 80);
+    assert_eq!(offer.token_a_offered_amount, 100);
+    assert_eq!(offer.bump, 254);
+    assert_eq!(offer.rent_payer, maker);
+    assert_eq!(offer.custody, Custody::Vault);
+    assert_eq!(offer.payout, maker);
+    assert_eq!(offer.fill_policy, FillPolicy::AllOrNothing);
+    assert_eq!(offer.token_a_filled_amount, 0);
+    assert_eq!(offer.fill_count, 0);
+    assert_eq!(offer.owner, None);
+    assert_eq!(offer.reserved, [0; OFFER_RESERVED_BYTES]);
+
+    // The migrated offer reads back with the current layout
+    let mut new_data = Vec::new();
+    offer.try_serialize(&mut new_data).unwrap();
+    let read_back = Offer::try_deserialize(&mut new_data.as_slice()).unwrap();
+    assert_eq!(read_back.maker, maker);
+    assert_eq!(read_back.version, OFFER_VERSION);
+
+    // Current layout accounts are bigger, so they can't be migrated again
+    new_data.resize(8 + Offer::INIT_SPACE, 0);
+    assert!(OfferV1::try_from_account_data(&new_data).is_err());
+}
+
+#[tokio::test]
+pub async fn migrate_offer_v1_account() {
+    println!("\n//// migrate_offer instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client, maker, ..
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let offer_id: u64 = 1;
+    let (offer_pda, bump) = Pubkey::find_program_address(
+        &[b"offer", FIXTURE_MAKER.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault = get_associated_token_address(&offer_pda, &FIXTURE_MINT_A);
+
+    // Assert the fixture holds the old layout
+    let offer_account = rpc_client.get_account(&offer_pda).await.unwrap();
+    assert_eq!(
+        offer_account.data,
+        offer_v1_account_data(&FIXTURE_MAKER, offer_id, bump)
+    );
+
+    ///////////// Migrate Offer /////////////
+    // Anyone can migrate an offer, paying the rent for the extra space
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::MigrateOffer {
+            payer: maker.pubkey(),
+            offer: offer_pda,
+            token_mint_a: FIXTURE_MINT_A,
+            vault,
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MigrateOffer {})
+        .send()
+        .await
+        .unwrap();
+
+    println!("MigrateOffer Successful with signature: {}", signature);
+
+    let offer: Offer = program.account(offer_pda).await.unwrap();
+    assert_eq!(offer.version, OFFER_VERSION);
+    assert_eq!(offer.id, offer_id);
+    assert_eq!(offer.maker, FIXTURE_MAKER);
+    assert_eq!(offer.token_a_offered_amount, 100);
+    assert_eq!(offer.token_b_amount_wanted, 80);
+    assert_eq!(offer.rent_payer, FIXTURE_MAKER);
+    assert_eq!(offer.payout, FIXTURE_MAKER);
+
+    let offer_account = rpc_client.get_account(&offer_pda).await.unwrap();
+    assert_eq!(offer_account.data.len(), 8 + Offer::INIT_SPACE);
+    assert!(
+        offer_account.lamports
+            >= rpc_client
+                .get_minimum_balance_for_rent_exemption(offer_account.data.len())
+                .await
+                .unwrap()
+    );
+
+    // A migrated offer can't be migrated again
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::MigrateOffer {
+            payer: maker.pubkey(),
+            offer: offer_pda,
+            token_mint_a: FIXTURE_MINT_A,
+            vault,
+            token_program: spl_token::id(),
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MigrateOffer {})
+        .send()
+        .await;
+
+    assert!(result.is_err(), "Migrated offer shouldn't migrate again");
+
+    println!();
+}
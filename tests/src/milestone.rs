@@ -0,0 +1,176 @@
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_client::{
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, SetupStruct};
+
+use escrow_app::{self, MilestoneTerms};
+
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+}
+
+#[tokio::test]
+pub async fn lock_and_release_milestones() {
+    println!("\n//// lock_milestones / release_milestone instructions ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_a_decimals,
+        maker_ata_a,
+        ..
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let escrow_id: u64 = 1;
+    let first_amount: u64 = ui_amount_to_amount(30.0, token_mint_a_decimals);
+    let second_amount: u64 = ui_amount_to_amount(70.0, token_mint_a_decimals);
+
+    let (escrow_pda, _) = Pubkey::find_program_address(
+        &[
+            b"milestones",
+            maker_pubkey.as_ref(),
+            &escrow_id.to_le_bytes(),
+        ],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&escrow_pda, &token_mint_a);
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+
+    ///////////// Lock Milestones /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::LockMilestones {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint: token_mint_a,
+            maker_token_account: maker_ata_a,
+            escrow: escrow_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::LockMilestones {
+            escrow_id,
+            beneficiary: taker_pubkey,
+            milestones: vec![
+                MilestoneTerms {
+                    amount: first_amount,
+                    release_at: now() + 3600,
+                },
+                MilestoneTerms {
+                    amount: second_amount,
+                    release_at: now() - 1,
+                },
+            ],
+        })
+        .send()
+        .await
+        .unwrap();
+
+    println!("LockMilestones Successful with signature: {}", signature);
+
+    // Assert vault balance == sum of the milestones
+    let vault_acc = rpc_client.get_account(&vault_ata).await.unwrap();
+    let vault_data = TokenAccount::unpack(&vault_acc.data).unwrap();
+    assert_eq!(vault_data.amount, first_amount + second_amount);
+
+    ///////////// Release the first milestone (maker, before it's due) /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::ReleaseMilestone {
+            authority: maker_pubkey,
+            payer: maker_pubkey,
+            maker: maker_pubkey,
+            beneficiary: taker_pubkey,
+            rent_payer: maker_pubkey,
+            token_mint: token_mint_a,
+            beneficiary_token_account: taker_ata_a,
+            escrow: escrow_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::ReleaseMilestone { index: 0 })
+        .send()
+        .await
+        .unwrap();
+
+    println!("ReleaseMilestone Successful with signature: {}", signature);
+
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, first_amount);
+
+    ///////////// Release the second milestone (beneficiary, once it's due) /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::ReleaseMilestone {
+            authority: taker_pubkey,
+            payer: taker_pubkey,
+            maker: maker_pubkey,
+            beneficiary: taker_pubkey,
+            rent_payer: maker_pubkey,
+            token_mint: token_mint_a,
+            beneficiary_token_account: taker_ata_a,
+            escrow: escrow_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::ReleaseMilestone { index: 1 })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("ReleaseMilestone Successful with signature: {}", signature);
+
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, first_amount + second_amount);
+
+    // Asset escrow PDA
+    let escrow_closed = rpc_client.get_account(&escrow_pda).await;
+    assert!(
+        escrow_closed.is_err(),
+        "Escrow account should be closed once every milestone is released"
+    );
+
+    println!();
+}
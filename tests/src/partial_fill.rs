@@ -0,0 +1,368 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::solana_program::sysvar,
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, take_offer_accounts, SetupStruct};
+
+use escrow_app::{self, state::Claim, FillPolicy};
+
+#[tokio::test]
+pub async fn take_offer_in_parts() {
+    println!("\n//// partial take_offer ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+    let maker_ata_b = get_associated_token_address(&maker_pubkey, &token_mint_b);
+
+    let min_fill_amount: u64 = ui_amount_to_amount(30.0, token_mint_a_decimals);
+    let first_fill_amount: u64 = ui_amount_to_amount(50.0, token_mint_a_decimals);
+
+    ///////////// Make Offer + Fill Policy (one transaction) /////////////
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    program
+        .request()
+        .instruction(make_offer_ix)
+        .accounts(escrow_app::accounts::SetOfferFillPolicy {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            token_mint_a,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferFillPolicy {
+            fill_policy: FillPolicy::Partial { min_fill_amount },
+        })
+        .send()
+        .await
+        .unwrap();
+
+    // Once the offer is live, the fill policy can't change under the taker
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::SetOfferFillPolicy {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            token_mint_a,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferFillPolicy {
+            fill_policy: FillPolicy::AllOrNothing,
+        })
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Fill policy shouldn't change on a live offer"
+    );
+
+    ///////////// Take Offer (first part) /////////////
+    let signature = program
+        .request()
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            token_mint_a,
+            token_mint_b,
+        ))
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: Some(first_fill_amount),
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the taker got their part, and paid for it pro rata
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, first_fill_amount);
+
+    let maker_ata_account_b = rpc_client.get_account(&maker_ata_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_ata_account_b.data).unwrap();
+    assert_eq!(maker_data_b.amount, token_b_amount_wanted / 2);
+
+    // Assert the offer is still live with the rest
+    let offer: escrow_app::state::Offer = program.account(offer_pda).await.unwrap();
+    assert_eq!(offer.token_a_filled_amount, first_fill_amount);
+
+    ///////////// Take Offer (leaving dust) /////////////
+    let result = program
+        .request()
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            token_mint_a,
+            token_mint_b,
+        ))
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: Some(min_fill_amount),
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "A fill leaving less than the minimum behind should be rejected"
+    );
+
+    ///////////// Take Offer (the rest) /////////////
+    let signature = program
+        .request()
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            token_mint_a,
+            token_mint_b,
+        ))
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    let maker_ata_account_b = rpc_client.get_account(&maker_ata_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_ata_account_b.data).unwrap();
+    assert_eq!(maker_data_b.amount, token_b_amount_wanted);
+
+    // Asset offer PDA
+    let offer_closed = rpc_client.get_account(&offer_pda).await;
+    assert!(
+        offer_closed.is_err(),
+        "Offer account should be closed once completely filled"
+    );
+
+    // Asset vault PDA
+    let vault_closed = rpc_client.get_account(&vault_ata).await;
+    assert!(
+        vault_closed.is_err(),
+        "Vault ATA should be closed once completely filled"
+    );
+
+    println!();
+}
+
+#[tokio::test]
+pub async fn take_offer_in_parts_through_claims() {
+    println!("\n//// partial take_offer settled through claims ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let min_fill_amount: u64 = ui_amount_to_amount(30.0, token_mint_a_decimals);
+    let fill_amount: u64 = ui_amount_to_amount(50.0, token_mint_a_decimals);
+
+    ///////////// Make Offer + Fill Policy (one transaction) /////////////
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    program
+        .request()
+        .instruction(make_offer_ix)
+        .accounts(escrow_app::accounts::SetOfferFillPolicy {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            token_mint_a,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferFillPolicy {
+            fill_policy: FillPolicy::Partial { min_fill_amount },
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Take Offer twice, parking each payment in its own claim /////////////
+    for fill_index in 0u32..2 {
+        let (claim_pda, _) = Pubkey::find_program_address(
+            &[b"claim", offer_pda.as_ref(), &fill_index.to_le_bytes()],
+            &program_id,
+        );
+        let claim_vault = get_associated_token_address(&claim_pda, &token_mint_b);
+
+        let signature = program
+            .request()
+            .accounts(escrow_app::accounts::TakeOffer {
+                claim: Some(claim_pda),
+                claim_vault: Some(claim_vault),
+                maker_token_account_b: None,
+                ..take_offer_accounts(
+                    taker_pubkey,
+                    maker_pubkey,
+                    offer_pda,
+                    token_mint_a,
+                    token_mint_b,
+                )
+            })
+            .args(escrow_app::instruction::TakeOffer {
+                preimage: None,
+                token_a_amount: Some(fill_amount),
+            })
+            .signer(taker.insecure_clone())
+            .send()
+            .await
+            .unwrap();
+
+        println!("TakeOffer Successful with signature: {}", signature);
+
+        // Assert each half of the payment waits in its own claim vault
+        let claim_vault_account = rpc_client.get_account(&claim_vault).await.unwrap();
+        let claim_vault_data = TokenAccount::unpack(&claim_vault_account.data).unwrap();
+        assert_eq!(claim_vault_data.amount, token_b_amount_wanted / 2);
+
+        let claim: Claim = program.account(claim_pda).await.unwrap();
+        assert_eq!(claim.offer, offer_pda);
+        assert_eq!(claim.fill_index, fill_index);
+    }
+
+    // Asset offer PDA
+    let offer_closed = rpc_client.get_account(&offer_pda).await;
+    assert!(
+        offer_closed.is_err(),
+        "Offer account should be closed once completely filled"
+    );
+
+    println!();
+}
@@ -0,0 +1,159 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{create_token_with_balance, initialize, take_offer_accounts, SetupStruct};
+
+use escrow_app::{
+    self,
+    state::{Offer, PaymentOption},
+};
+
+#[tokio::test]
+pub async fn take_offer_with_payment_option() {
+    println!("\n//// add_payment_option instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Token C is accepted as payment next to Token B, at its own price
+    let token_c_amount_wanted: u64 = 75;
+    let (token_mint_c, _) =
+        create_token_with_balance(&rpc_client, &taker, 0, token_c_amount_wanted).await;
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+    let maker_ata_c = get_associated_token_address(&maker_pubkey, &token_mint_c);
+
+    ///////////// Make Offer + Add Payment Option (one transaction) /////////////
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    let signature = program
+        .request()
+        .instruction(make_offer_ix)
+        .accounts(escrow_app::accounts::AddPaymentOption {
+            owner: maker_pubkey,
+            mint: token_mint_c,
+            offer: offer_pda,
+            token_program: spl_token::id(),
+        })
+        .args(escrow_app::instruction::AddPaymentOption {
+            amount: token_c_amount_wanted,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    println!("Offer made with signature: {}", signature);
+
+    // Asset Offer info
+    let offer_account = rpc_client.get_account(&offer_pda).await.unwrap();
+    let offer = Offer::try_deserialize(&mut offer_account.data.as_slice()).unwrap();
+
+    assert_eq!(
+        offer.payment_options,
+        vec![PaymentOption {
+            mint: token_mint_c,
+            amount: token_c_amount_wanted
+        }]
+    );
+
+    ///////////// Take Offer, paying with Token C /////////////
+    let signature = program
+        .request()
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
+            token_mint_a,
+            token_mint_c,
+        ))
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the maker got the Token C price
+    let maker_ata_account_c = rpc_client.get_account(&maker_ata_c).await.unwrap();
+    let maker_data_c = TokenAccount::unpack(&maker_ata_account_c.data).unwrap();
+    assert_eq!(maker_data_c.amount, token_c_amount_wanted);
+
+    // Assert the taker got Token A
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    println!();
+}
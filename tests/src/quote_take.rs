@@ -0,0 +1,147 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, simulate_quote, SetupStruct};
+
+use escrow_app::{self, error::ErrorCode, TakeQuote};
+
+#[tokio::test]
+pub async fn quote_take() {
+    println!("\n//// quote_take ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    ///////////// Make Offer /////////////
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    let quote_take_ix = |token_a_amount: Option<u64>| {
+        program
+            .request()
+            .accounts(escrow_app::accounts::QuoteTake {
+                taker: taker_pubkey,
+                recipient: taker_pubkey,
+                payout: maker_pubkey,
+                rent_payer: maker_pubkey,
+                maker: maker_pubkey,
+                token_mint_a,
+                token_mint_b,
+                token_b_metadata: None,
+                taker_token_account_b: Some(taker_ata_b),
+                offer: offer_pda,
+                maker_token_account_a: None,
+                delegate: None,
+                registry: None,
+                attestation: None,
+                gate_token_account: None,
+                token_program: spl_token::id(),
+            })
+            .args(escrow_app::instruction::QuoteTake {
+                preimage: None,
+                token_a_amount,
+            })
+            .instructions()
+            .unwrap()
+            .remove(0)
+    };
+
+    ///////////// Quote the whole offer /////////////
+    let quote = simulate_quote(&rpc_client, quote_take_ix(None), &taker).await;
+
+    assert_eq!(
+        quote,
+        TakeQuote {
+            token_a_amount: token_a_offered_amount,
+            token_b_amount: token_b_amount_wanted,
+            arbiter_fee: 0,
+            token_a_remaining_amount: 0,
+            basket_wanted: vec![],
+            basket_offered: vec![],
+            error: None,
+        }
+    );
+
+    ///////////// Quote a partial take of an all-or-nothing offer /////////////
+    let partial_amount = ui_amount_to_amount(50.0, token_mint_a_decimals);
+    let quote = simulate_quote(&rpc_client, quote_take_ix(Some(partial_amount)), &taker).await;
+
+    assert_eq!(
+        quote,
+        TakeQuote {
+            error: Some(ErrorCode::PartialFillNotAllowed.into()),
+            ..TakeQuote::default()
+        }
+    );
+
+    // Assert quoting moved nothing
+    let vault_account = rpc_client.get_account(&vault_ata).await.unwrap();
+    let vault_data = TokenAccount::unpack(&vault_account.data).unwrap();
+    assert_eq!(vault_data.amount, token_a_offered_amount);
+}
@@ -0,0 +1,163 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, SetupStruct};
+
+use escrow_app::{self, state::Offer};
+
+#[tokio::test]
+pub async fn make_and_replace_offer() {
+    println!("\n//// replace_offer instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker: _,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let new_offer_id: u64 = 2;
+    let new_token_a_offered_amount: u64 = ui_amount_to_amount(60.0, token_mint_a_decimals);
+    let new_token_b_amount_wanted: u64 = ui_amount_to_amount(50.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+    let (new_offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &new_offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+    let new_vault_ata = get_associated_token_address(&new_offer_pda, &token_mint_a);
+
+    // Send transaction via Anchor client (Make Offer)
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Replace Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::ReplaceOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            rent_payer: maker_pubkey,
+            token_mint_a,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            new_token_mint_a: token_mint_a,
+            new_token_mint_b: token_mint_b,
+            maker_new_token_account_a: maker_ata_a,
+            new_offer: new_offer_pda,
+            new_vault: new_vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::ReplaceOffer {
+            new_offer_id,
+            token_a_offered_amount: new_token_a_offered_amount,
+            token_b_amount_wanted: new_token_b_amount_wanted,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    println!("ReplaceOffer Successful with signature: {}", signature);
+
+    // Assert new vault balance == new_token_a_offered_amount
+    let new_vault_acc = rpc_client.get_account(&new_vault_ata).await.unwrap();
+    let new_vault_data = TokenAccount::unpack(&new_vault_acc.data).unwrap();
+    assert_eq!(new_vault_data.amount, new_token_a_offered_amount);
+
+    // Assert the difference went back to the maker
+    let maker_acc = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data = TokenAccount::unpack(&maker_acc.data).unwrap();
+    assert_eq!(
+        maker_data.amount,
+        token_a_offered_amount - new_token_a_offered_amount
+    );
+
+    // Asset new Offer info
+    let new_offer_account = rpc_client.get_account(&new_offer_pda).await.unwrap();
+    let new_offer = Offer::try_deserialize(&mut new_offer_account.data.as_slice()).unwrap();
+
+    assert_eq!(new_offer.id, new_offer_id);
+    assert_eq!(new_offer.maker, maker_pubkey);
+    assert_eq!(new_offer.token_mint_a, token_mint_a);
+    assert_eq!(new_offer.token_mint_b, token_mint_b);
+    assert_eq!(new_offer.token_b_amount_wanted, new_token_b_amount_wanted);
+
+    // Asset old offer PDA
+    let offer_closed = rpc_client.get_account(&offer_pda).await;
+    assert!(
+        offer_closed.is_err(),
+        "Old offer account should be closed after replace"
+    );
+
+    // Asset old vault PDA
+    let vault_closed = rpc_client.get_account(&vault_ata).await;
+    assert!(
+        vault_closed.is_err(),
+        "Old vault ATA should be closed after replace"
+    );
+
+    println!();
+}
@@ -0,0 +1,167 @@
+use std::{
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use anchor_client::{
+    anchor_lang::AccountDeserialize,
+    solana_sdk::{
+        self,
+        commitment_config::CommitmentConfig,
+        signature::{Keypair, Signer},
+    },
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, SetupStruct};
+
+use escrow_app::{self, state::Offer};
+
+#[tokio::test]
+pub async fn set_manager_and_cancel_offer_as_manager() {
+    println!("\n//// set_offer_manager instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker: _,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+
+    // A session key for the bot; the maker pays the fees so it doesn't need any SOL
+    let manager = Keypair::new();
+    let manager_pubkey = manager.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+    let manager_expires_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_secs() as i64
+        + 3600;
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    // Send transaction via Anchor client (Make Offer)
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Set Offer Manager /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::SetOfferManager {
+            owner: maker_pubkey,
+            offer: offer_pda,
+        })
+        .args(escrow_app::instruction::SetOfferManager {
+            manager: Some(manager_pubkey),
+            manager_expires_at,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    println!("SetOfferManager Successful with signature: {}", signature);
+
+    // Asset Offer info
+    let offer_account = rpc_client.get_account(&offer_pda).await.unwrap();
+    let offer = Offer::try_deserialize(&mut offer_account.data.as_slice()).unwrap();
+
+    assert_eq!(offer.manager, Some(manager_pubkey));
+    assert_eq!(offer.manager_expires_at, manager_expires_at);
+
+    ///////////// Cancel Offer (as manager) /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::CancelOffer {
+            authority: manager_pubkey,
+            payer: maker_pubkey,
+            maker: maker_pubkey,
+            owner: maker_pubkey,
+            rent_payer: maker_pubkey,
+            token_mint_a,
+            owner_token_account_a: Some(maker_ata_a),
+            other_owner_token_account_a: None,
+            offer: offer_pda,
+            vault: Some(vault_ata),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::CancelOffer {})
+        .signer(manager)
+        .send()
+        .await
+        .unwrap();
+
+    println!(
+        "CancelOffer by manager Successful with signature: {}",
+        signature
+    );
+
+    // Assert tokens went back to the maker, not the manager
+    let maker_ata_account = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data = TokenAccount::unpack(&maker_ata_account.data).unwrap();
+    assert_eq!(maker_data.amount, token_a_offered_amount);
+
+    // Asset offer PDA
+    let offer_closed = rpc_client.get_account(&offer_pda).await;
+    assert!(
+        offer_closed.is_err(),
+        "Offer account should be closed after cancel"
+    );
+
+    println!();
+}
@@ -0,0 +1,237 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    solana_sdk::{
+        self, commitment_config::CommitmentConfig, signature::Signer, transaction::Transaction,
+    },
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{create_token_with_balance, initialize, SetupStruct};
+
+#[tokio::test]
+pub async fn sweep_stray_tokens() {
+    println!("\n//// sweep ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    ///////////// Make Offer /////////////
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Someone sends another token to the offer PDA /////////////
+    let stray_amount: u64 = 25;
+    let (stray_mint, taker_stray_ata) =
+        create_token_with_balance(&rpc_client, &taker, 0, stray_amount).await;
+    let offer_stray_ata = get_associated_token_address(&offer_pda, &stray_mint);
+
+    let create_ata_ix = spl_associated_token_account::instruction::create_associated_token_account(
+        &taker_pubkey,
+        &offer_pda,
+        &stray_mint,
+        &spl_token::id(),
+    );
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &taker_stray_ata,
+        &offer_stray_ata,
+        &taker_pubkey,
+        &[],
+        stray_amount,
+    )
+    .unwrap();
+
+    let recent_blockhash = rpc_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_ata_ix, transfer_ix],
+        Some(&taker_pubkey),
+        &[&taker],
+        recent_blockhash,
+    );
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .unwrap();
+
+    ///////////// Sweep the stray tokens /////////////
+    let maker_stray_ata = get_associated_token_address(&maker_pubkey, &stray_mint);
+
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::Sweep {
+            owner: maker_pubkey,
+            payer: maker_pubkey,
+            mint: stray_mint,
+            offer: offer_pda,
+            token_account: offer_stray_ata,
+            owner_token_account: maker_stray_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::Sweep {})
+        .send()
+        .await
+        .unwrap();
+
+    println!("Sweep Successful with signature: {}", signature);
+
+    // Assert the maker got the stray tokens, and the account is closed
+    let maker_stray_account = rpc_client.get_account(&maker_stray_ata).await.unwrap();
+    let maker_stray_data = TokenAccount::unpack(&maker_stray_account.data).unwrap();
+    assert_eq!(maker_stray_data.amount, stray_amount);
+
+    let stray_closed = rpc_client.get_account(&offer_stray_ata).await;
+    assert!(
+        stray_closed.is_err(),
+        "Stray token account should be closed after sweep"
+    );
+
+    ///////////// Sweep the vault /////////////
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::Sweep {
+            owner: maker_pubkey,
+            payer: maker_pubkey,
+            mint: token_mint_a,
+            offer: offer_pda,
+            token_account: vault_ata,
+            owner_token_account: maker_ata_a,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::Sweep {})
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Vault holding only the offered tokens shouldn't be swept"
+    );
+
+    // Assert the offered tokens are still in the vault
+    let vault_account = rpc_client.get_account(&vault_ata).await.unwrap();
+    let vault_data = TokenAccount::unpack(&vault_account.data).unwrap();
+    assert_eq!(vault_data.amount, token_a_offered_amount);
+
+    ///////////// Someone sends more of token A to the vault /////////////
+    let surplus_amount: u64 = ui_amount_to_amount(5.0, token_mint_a_decimals);
+    let transfer_ix = spl_token::instruction::transfer(
+        &spl_token::id(),
+        &maker_ata_a,
+        &vault_ata,
+        &maker_pubkey,
+        &[],
+        surplus_amount,
+    )
+    .unwrap();
+
+    let recent_blockhash = rpc_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[transfer_ix],
+        Some(&maker_pubkey),
+        &[&maker],
+        recent_blockhash,
+    );
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .unwrap();
+
+    let maker_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_balance_a = TokenAccount::unpack(&maker_account_a.data).unwrap().amount;
+
+    ///////////// Sweep the surplus /////////////
+    program
+        .request()
+        .accounts(escrow_app::accounts::Sweep {
+            owner: maker_pubkey,
+            payer: maker_pubkey,
+            mint: token_mint_a,
+            offer: offer_pda,
+            token_account: vault_ata,
+            owner_token_account: maker_ata_a,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::Sweep {})
+        .send()
+        .await
+        .unwrap();
+
+    // Assert only the surplus was swept, and the vault is still open
+    let maker_account_a = rpc_client.get_account(&maker_ata_a).await.unwrap();
+    let maker_data_a = TokenAccount::unpack(&maker_account_a.data).unwrap();
+    assert_eq!(maker_data_a.amount, maker_balance_a + surplus_amount);
+
+    let vault_account = rpc_client.get_account(&vault_ata).await.unwrap();
+    let vault_data = TokenAccount::unpack(&vault_account.data).unwrap();
+    assert_eq!(vault_data.amount, token_a_offered_amount);
+
+    println!();
+}
@@ -1,7 +1,11 @@
 use std::str::FromStr;
 
 use anchor_client::{
-    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    solana_sdk::{
+        self,
+        commitment_config::CommitmentConfig,
+        signature::{Keypair, Signer},
+    },
     Cluster,
 };
 use spl_associated_token_account::get_associated_token_address;
@@ -11,9 +15,7 @@ use spl_token::{
     ui_amount_to_amount,
 };
 
-use crate::utils::{initialize, SetupStruct};
-
-use escrow_app;
+use crate::utils::{create_token_account, initialize, take_offer_accounts, SetupStruct};
 
 #[tokio::test]
 pub async fn make_and_take_offer() {
@@ -64,6 +66,7 @@ pub async fn make_and_take_offer() {
         .request()
         .accounts(escrow_app::accounts::MakeOffer {
             maker: maker_pubkey,
+            payer: maker_pubkey,
             token_mint_a,
             token_mint_b,
             maker_token_account_a: maker_ata_a,
@@ -77,6 +80,8 @@ pub async fn make_and_take_offer() {
             offer_id,
             token_a_offered_amount,
             token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
         })
         .send()
         .await
@@ -87,21 +92,17 @@ pub async fn make_and_take_offer() {
 
     let signature = program
         .request()
-        .accounts(escrow_app::accounts::TakeOffer {
-            taker: taker_pubkey,
-            maker: maker_pubkey,
+        .accounts(take_offer_accounts(
+            taker_pubkey,
+            maker_pubkey,
+            offer_pda,
             token_mint_a,
             token_mint_b,
-            taker_token_account_a: taker_ata_a,
-            taker_token_account_b: taker_ata_b,
-            maker_token_account_b: maker_ata_b,
-            offer: offer_pda,
-            vault: vault_ata,
-            token_program: spl_token::id(),
-            associated_token_program: spl_associated_token_account::ID,
-            system_program: solana_sdk::system_program::id(),
+        ))
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
         })
-        .args(escrow_app::instruction::TakeOffer {})
         .signer(taker)
         .send()
         .await
@@ -149,3 +150,230 @@ pub async fn make_and_take_offer() {
 
     println!();
 }
+
+#[tokio::test]
+pub async fn take_offer_with_payout_and_recipient() {
+    println!("\n//// take_offer instruction (payout and recipient wallets) ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Cold wallets receiving the proceeds on each side; they never sign
+    let payout_pubkey = Keypair::new().pubkey();
+    let recipient_pubkey = Keypair::new().pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let recipient_ata_a = get_associated_token_address(&recipient_pubkey, &token_mint_a);
+    let payout_ata_b = get_associated_token_address(&payout_pubkey, &token_mint_b);
+
+    // Send transaction via Anchor client (Make Offer)
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: Some(payout_pubkey),
+            hashlock: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            recipient: recipient_pubkey,
+            payout: payout_pubkey,
+            taker_token_account_a: Some(recipient_ata_a),
+            maker_token_account_b: Some(payout_ata_b),
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker)
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the payout wallet got token mint b
+    let payout_ata_account_b = rpc_client.get_account(&payout_ata_b).await.unwrap();
+    let payout_data_b = TokenAccount::unpack(&payout_ata_account_b.data).unwrap();
+    assert_eq!(payout_data_b.amount, token_b_amount_wanted);
+
+    // Assert the recipient wallet got token mint a
+    let recipient_ata_account_a = rpc_client.get_account(&recipient_ata_a).await.unwrap();
+    let recipient_data_a = TokenAccount::unpack(&recipient_ata_account_a.data).unwrap();
+    assert_eq!(recipient_data_a.amount, token_a_offered_amount);
+
+    println!();
+}
+
+#[tokio::test]
+pub async fn take_offer_with_non_ata_token_accounts() {
+    println!("\n//// take_offer instruction (non-ATA token accounts) ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    // Token accounts living outside the ATAs on both sides
+    let taker_account_a =
+        create_token_account(&rpc_client, &taker, &token_mint_a, &taker_pubkey).await;
+    let maker_account_b =
+        create_token_account(&rpc_client, &maker, &token_mint_b, &maker_pubkey).await;
+
+    // Send transaction via Anchor client (Make Offer)
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            other_taker_token_account_a: Some(taker_account_a),
+            other_maker_token_account_b: Some(maker_account_b),
+            taker_token_account_a: None,
+            maker_token_account_b: None,
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker)
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the maker's token account got token mint b
+    let maker_account_b_data = rpc_client.get_account(&maker_account_b).await.unwrap();
+    let maker_data_b = TokenAccount::unpack(&maker_account_b_data.data).unwrap();
+    assert_eq!(maker_data_b.amount, token_b_amount_wanted);
+
+    // Assert the taker's token account got token mint a
+    let taker_account_a_data = rpc_client.get_account(&taker_account_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_account_a_data.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    println!();
+}
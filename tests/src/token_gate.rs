@@ -0,0 +1,156 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    anchor_lang::solana_program::sysvar,
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{create_token_with_balance, initialize, take_offer_accounts, SetupStruct};
+
+use escrow_app::{self, TokenGate};
+
+#[tokio::test]
+pub async fn take_offer_with_token_gate() {
+    println!("\n//// take_offer instruction (token-gated offer) ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+
+    // Membership token held by the taker
+    let (gate_mint, taker_gate_ata) = create_token_with_balance(&rpc_client, &taker, 0, 1).await;
+
+    ///////////// Make Offer + Token Gate (one transaction) /////////////
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    program
+        .request()
+        .instruction(make_offer_ix)
+        .accounts(escrow_app::accounts::SetOfferGate {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferGate {
+            token_gate: Some(TokenGate {
+                mint: gate_mint,
+                min_balance: 1,
+            }),
+        })
+        .send()
+        .await
+        .unwrap();
+
+    // Once the offer is live, the gate can't be lifted
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::SetOfferGate {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferGate { token_gate: None })
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Token gate shouldn't be lifted on a live offer"
+    );
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            gate_token_account: Some(taker_gate_ata),
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert taker's token's balance of token mint a
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    println!();
+}
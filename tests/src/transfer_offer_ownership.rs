@@ -0,0 +1,179 @@
+use std::str::FromStr;
+
+use anchor_client::{
+    solana_sdk::{
+        self,
+        commitment_config::CommitmentConfig,
+        signature::{Keypair, Signer},
+    },
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, take_offer_accounts, SetupStruct};
+
+#[tokio::test]
+pub async fn transfer_offer_ownership() {
+    println!("\n//// transfer_offer_ownership ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+    let new_owner_pubkey = Keypair::new().pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+    let new_owner_ata_b = get_associated_token_address(&new_owner_pubkey, &token_mint_b);
+
+    ///////////// Make Offer /////////////
+    program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    ///////////// Transfer Offer Ownership /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TransferOfferOwnership {
+            owner: maker_pubkey,
+            offer: offer_pda,
+        })
+        .args(escrow_app::instruction::TransferOfferOwnership {
+            new_owner: new_owner_pubkey,
+            payout: None,
+        })
+        .send()
+        .await
+        .unwrap();
+
+    println!(
+        "TransferOfferOwnership Successful with signature: {}",
+        signature
+    );
+
+    // Assert the offer keeps its address, now owned and paid out to the new owner
+    let offer: escrow_app::state::Offer = program.account(offer_pda).await.unwrap();
+    assert_eq!(offer.maker, maker_pubkey);
+    assert_eq!(offer.current_owner(), new_owner_pubkey);
+    assert_eq!(offer.payout, new_owner_pubkey);
+
+    ///////////// Cancel Offer (by the former owner) /////////////
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::CancelOffer {
+            authority: maker_pubkey,
+            payer: maker_pubkey,
+            maker: maker_pubkey,
+            owner: maker_pubkey,
+            rent_payer: maker_pubkey,
+            token_mint_a,
+            owner_token_account_a: Some(maker_ata_a),
+            other_owner_token_account_a: None,
+            offer: offer_pda,
+            vault: Some(vault_ata),
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::CancelOffer {})
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "The former owner shouldn't be able to cancel the offer"
+    );
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            payout: new_owner_pubkey,
+            maker_token_account_b: Some(new_owner_ata_b),
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert the taker got token A and the new owner got paid
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    let new_owner_ata_account_b = rpc_client.get_account(&new_owner_ata_b).await.unwrap();
+    let new_owner_data_b = TokenAccount::unpack(&new_owner_ata_account_b.data).unwrap();
+    assert_eq!(new_owner_data_b.amount, token_b_amount_wanted);
+
+    println!();
+}
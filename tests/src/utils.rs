@@ -1,12 +1,26 @@
 use std::{thread::sleep, time::Duration};
 
-use anchor_client::solana_sdk::{
-    commitment_config::CommitmentConfig, native_token::sol_to_lamports, program_pack::Pack,
-    signature::Keypair, signer::Signer, transaction::Transaction,
+use anchor_client::{
+    anchor_lang::AnchorDeserialize,
+    solana_sdk::{
+        commitment_config::CommitmentConfig, instruction::Instruction,
+        native_token::sol_to_lamports, program_pack::Pack, signature::Keypair, signer::Signer,
+        transaction::Transaction,
+    },
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use escrow_app::TakeQuote;
+use mpl_token_metadata::{
+    accounts::{MasterEdition, Metadata},
+    instructions::{CreateV1Builder, MintV1Builder, VerifyCollectionV1Builder},
+    types::{Collection, TokenStandard},
 };
 use solana_client::nonblocking::rpc_client::RpcClient;
 use spl_associated_token_account::get_associated_token_address;
-use spl_token::{id, solana_program::pubkey::Pubkey, state::Mint, ui_amount_to_amount};
+use spl_token::{
+    id, instruction::AuthorityType, solana_program::pubkey::Pubkey, state::Mint,
+    ui_amount_to_amount,
+};
 
 pub struct SetupStruct {
     pub rpc_client: RpcClient,
@@ -53,11 +67,11 @@ pub async fn initialize() -> SetupStruct {
     println!("Airdropped 5 SOL to Taker: {}", taker.pubkey());
 
     let (token_mint_a, token_mint_a_decimals) =
-        create_token_mint(&rpc_client, &token_mint_authority).await;
+        create_token_mint(&rpc_client, &token_mint_authority, 10).await;
     println!("Created Token Mint A: {}", token_mint_a);
 
     let (token_mint_b, token_mint_b_decimals) =
-        create_token_mint(&rpc_client, &token_mint_authority).await;
+        create_token_mint(&rpc_client, &token_mint_authority, 10).await;
     println!("Created Token Mint B: {}", token_mint_b);
 
     // Get or create maker's ATA for Token A
@@ -90,7 +104,7 @@ pub async fn initialize() -> SetupStruct {
     .await;
     println!("Minted 80 Token B to Taker's ATA.");
 
-    return SetupStruct {
+    SetupStruct {
         rpc_client,
         maker,
         taker,
@@ -100,13 +114,179 @@ pub async fn initialize() -> SetupStruct {
         token_mint_b_decimals,
         maker_ata_a,
         taker_ata_b,
-    };
+    }
+}
+
+/// Creates an NFT (0 decimals, supply of 1, mint authority revoked) owned by `owner`
+/// and returns its mint and the owner's ATA holding it.
+pub async fn create_nft(rpc_client: &RpcClient, owner: &Keypair) -> (Pubkey, Pubkey) {
+    let (token_mint, owner_ata, token_mint_authority) =
+        create_token_with_authority(rpc_client, owner, 0, 1).await;
+
+    let revoke_ix = spl_token::instruction::set_authority(
+        &id(),
+        &token_mint,
+        None,
+        AuthorityType::MintTokens,
+        &token_mint_authority.pubkey(),
+        &[],
+    )
+    .unwrap();
+    send_instructions(rpc_client, &[revoke_ix], &token_mint_authority, &[]).await;
+
+    (token_mint, owner_ata)
+}
+
+/// Creates a Metaplex NFT held by `owner`, with `update_authority` as its update
+/// authority and, if given, an unverified member of `collection`. Its master edition
+/// holds the mint authority. Returns its mint.
+pub async fn create_metaplex_nft(
+    rpc_client: &RpcClient,
+    update_authority: &Keypair,
+    owner: &Pubkey,
+    collection: Option<Pubkey>,
+) -> Pubkey {
+    let mint = Keypair::new();
+    let mint_pubkey = mint.pubkey();
+    let update_authority_pubkey = update_authority.pubkey();
+    let (metadata, _) = Metadata::find_pda(&mint_pubkey);
+    let (master_edition, _) = MasterEdition::find_pda(&mint_pubkey);
+
+    let mut create = CreateV1Builder::new();
+    create
+        .metadata(metadata)
+        .master_edition(Some(master_edition))
+        .mint(mint_pubkey, true)
+        .authority(update_authority_pubkey)
+        .payer(update_authority_pubkey)
+        .update_authority(update_authority_pubkey, true)
+        .spl_token_program(Some(id()))
+        .name(String::from("Test NFT"))
+        .uri(String::from("https://example.com/nft.json"))
+        .seller_fee_basis_points(0)
+        .token_standard(TokenStandard::NonFungible);
+    if let Some(key) = collection {
+        create.collection(Collection {
+            verified: false,
+            key,
+        });
+    }
+
+    let mint_ix = MintV1Builder::new()
+        .token(get_associated_token_address(owner, &mint_pubkey))
+        .token_owner(Some(*owner))
+        .metadata(metadata)
+        .master_edition(Some(master_edition))
+        .mint(mint_pubkey)
+        .authority(update_authority_pubkey)
+        .payer(update_authority_pubkey)
+        .amount(1)
+        .instruction();
+
+    send_instructions(
+        rpc_client,
+        &[create.instruction(), mint_ix],
+        update_authority,
+        &[&mint],
+    )
+    .await;
+
+    mint_pubkey
+}
+
+/// Verifies `nft_mint` as a member of `collection_mint`, signed by the collection's
+/// update authority.
+pub async fn verify_collection(
+    rpc_client: &RpcClient,
+    collection_authority: &Keypair,
+    nft_mint: &Pubkey,
+    collection_mint: &Pubkey,
+) {
+    let verify_ix = VerifyCollectionV1Builder::new()
+        .authority(collection_authority.pubkey())
+        .metadata(Metadata::find_pda(nft_mint).0)
+        .collection_mint(*collection_mint)
+        .collection_metadata(Some(Metadata::find_pda(collection_mint).0))
+        .collection_master_edition(Some(MasterEdition::find_pda(collection_mint).0))
+        .instruction();
+
+    send_instructions(rpc_client, &[verify_ix], collection_authority, &[]).await;
 }
 
-async fn create_token_mint(rpc_client: &RpcClient, token_mint_authority: &Keypair) -> (Pubkey, u8) {
+/// Creates a new token and mints `amount` of it to `owner`. Returns the mint and
+/// the owner's ATA holding the tokens.
+pub async fn create_token_with_balance(
+    rpc_client: &RpcClient,
+    owner: &Keypair,
+    decimals: u8,
+    amount: u64,
+) -> (Pubkey, Pubkey) {
+    let (token_mint, owner_ata, _) =
+        create_token_with_authority(rpc_client, owner, decimals, amount).await;
+    (token_mint, owner_ata)
+}
+
+/// Like `create_token_with_balance`, also returning the mint authority.
+async fn create_token_with_authority(
+    rpc_client: &RpcClient,
+    owner: &Keypair,
+    decimals: u8,
+    amount: u64,
+) -> (Pubkey, Pubkey, Keypair) {
+    let token_mint_authority = Keypair::new();
+    rpc_client
+        .request_airdrop(&token_mint_authority.pubkey(), sol_to_lamports(1.0))
+        .await
+        .unwrap();
+
+    sleep(Duration::from_secs(1));
+
+    let (token_mint, _) = create_token_mint(rpc_client, &token_mint_authority, decimals).await;
+    let owner_ata = get_or_create_ata(rpc_client, owner, &token_mint).await;
+    mint_to_ata(
+        rpc_client,
+        &token_mint_authority,
+        &token_mint,
+        &owner_ata,
+        amount,
+    )
+    .await;
+
+    (token_mint, owner_ata, token_mint_authority)
+}
+
+/// Sends `instructions` in one transaction paid by `payer`, also signed by `signers`.
+async fn send_instructions(
+    rpc_client: &RpcClient,
+    instructions: &[Instruction],
+    payer: &Keypair,
+    signers: &[&Keypair],
+) {
+    let recent_blockhash = rpc_client.get_latest_blockhash().await.unwrap();
+    let mut all_signers = vec![payer];
+    all_signers.extend_from_slice(signers);
+
+    let transaction = Transaction::new_signed_with_payer(
+        instructions,
+        Some(&payer.pubkey()),
+        &all_signers,
+        recent_blockhash,
+    );
+
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .unwrap();
+}
+
+async fn create_token_mint(
+    rpc_client: &RpcClient,
+    token_mint_authority: &Keypair,
+    decimals: u8,
+) -> (Pubkey, u8) {
     let token_mint_authority_pubkey = token_mint_authority.pubkey();
 
-    let (token_program_id, decimals) = (id(), 10 as u8);
+    let token_program_id = id();
     let space = Mint::LEN;
     let rent = rpc_client
         .get_minimum_balance_for_rent_exemption(space)
@@ -140,7 +320,7 @@ async fn create_token_mint(rpc_client: &RpcClient, token_mint_authority: &Keypai
     let transaction = Transaction::new_signed_with_payer(
         &[token_mint_instruction.clone(), token_mint_ix],
         Some(&token_mint_authority_pubkey),
-        &[&token_mint_authority, &token_mint_account],
+        &[token_mint_authority, &token_mint_account],
         recent_blockhash,
     );
 
@@ -149,10 +329,10 @@ async fn create_token_mint(rpc_client: &RpcClient, token_mint_authority: &Keypai
         .await
         .unwrap();
 
-    return (token_mint_account.pubkey(), decimals);
+    (token_mint_account.pubkey(), decimals)
 }
 
-async fn get_or_create_ata(rpc_client: &RpcClient, owner: &Keypair, mint: &Pubkey) -> Pubkey {
+pub async fn get_or_create_ata(rpc_client: &RpcClient, owner: &Keypair, mint: &Pubkey) -> Pubkey {
     let owner_pubkey = owner.pubkey();
     let ata_pubkey = get_associated_token_address(&owner_pubkey, mint);
 
@@ -186,6 +366,58 @@ async fn get_or_create_ata(rpc_client: &RpcClient, owner: &Keypair, mint: &Pubke
     ata_pubkey
 }
 
+/// Creates a token account for `mint` owned by `owner` at a fresh address, i.e.
+/// one that isn't an ATA. `payer` pays for the rent.
+pub async fn create_token_account(
+    rpc_client: &RpcClient,
+    payer: &Keypair,
+    mint: &Pubkey,
+    owner: &Pubkey,
+) -> Pubkey {
+    let payer_pubkey = payer.pubkey();
+
+    let token_program_id = id();
+    let space = spl_token::state::Account::LEN;
+    let rent = rpc_client
+        .get_minimum_balance_for_rent_exemption(space)
+        .await
+        .unwrap();
+
+    let token_account = Keypair::new();
+
+    let create_account_ix = solana_system_interface::instruction::create_account(
+        &payer_pubkey,
+        &token_account.pubkey(),
+        rent,
+        space as u64,
+        &token_program_id,
+    );
+
+    let initialize_account_ix = spl_token::instruction::initialize_account3(
+        &token_program_id,
+        &token_account.pubkey(),
+        mint,
+        owner,
+    )
+    .unwrap();
+
+    let recent_blockhash = rpc_client.get_latest_blockhash().await.unwrap();
+
+    let transaction = Transaction::new_signed_with_payer(
+        &[create_account_ix, initialize_account_ix],
+        Some(&payer_pubkey),
+        &[payer, &token_account],
+        recent_blockhash,
+    );
+
+    rpc_client
+        .send_and_confirm_transaction(&transaction)
+        .await
+        .unwrap();
+
+    token_account.pubkey()
+}
+
 async fn mint_to_ata(
     rpc_client: &RpcClient,
     mint_authority: &Keypair,
@@ -219,3 +451,80 @@ async fn mint_to_ata(
         .await
         .unwrap();
 }
+
+/// The accounts of a plain `take_offer`: a vault offer paid for from and into ATAs,
+/// with the taker receiving the offered tokens and every optional feature left out.
+/// Tests override what they need with struct update syntax.
+pub fn take_offer_accounts(
+    taker: Pubkey,
+    maker: Pubkey,
+    offer: Pubkey,
+    token_mint_a: Pubkey,
+    token_mint_b: Pubkey,
+) -> escrow_app::accounts::TakeOffer {
+    escrow_app::accounts::TakeOffer {
+        taker,
+        recipient: taker,
+        payer: taker,
+        maker,
+        payout: maker,
+        rent_payer: maker,
+        token_mint_a,
+        token_mint_b,
+        token_b_metadata: None,
+        taker_token_account_a: Some(get_associated_token_address(&taker, &token_mint_a)),
+        other_taker_token_account_a: None,
+        taker_token_account_b: get_associated_token_address(&taker, &token_mint_b),
+        maker_token_account_b: Some(get_associated_token_address(&maker, &token_mint_b)),
+        other_maker_token_account_b: None,
+        claim: None,
+        claim_vault: None,
+        vesting: None,
+        vesting_vault: None,
+        arbitration: None,
+        arbitration_vault: None,
+        arbitration_vault_a: None,
+        offer,
+        vault: Some(get_associated_token_address(&offer, &token_mint_a)),
+        maker_token_account_a: None,
+        delegate: None,
+        registry: None,
+        attestation: None,
+        gate_token_account: None,
+        token_program: id(),
+        associated_token_program: spl_associated_token_account::ID,
+        system_program: anchor_client::solana_sdk::system_program::id(),
+    }
+}
+
+/// The serialized size of a `TakeQuote` without basket legs, carrying an error code.
+const TAKE_QUOTE_SIZE: usize = 4 * 8 + 2 * 4 + 1 + 4;
+
+/// Simulates `quote_take_ix` and decodes the quote it returns.
+pub async fn simulate_quote(
+    rpc_client: &RpcClient,
+    quote_take_ix: Instruction,
+    payer: &Keypair,
+) -> TakeQuote {
+    let recent_blockhash = rpc_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[quote_take_ix],
+        Some(&payer.pubkey()),
+        &[payer],
+        recent_blockhash,
+    );
+
+    let simulation = rpc_client
+        .simulate_transaction(&transaction)
+        .await
+        .unwrap()
+        .value;
+    assert!(simulation.err.is_none(), "{:?}", simulation.logs);
+
+    let (data, _) = simulation.return_data.unwrap().data;
+    let mut data = STANDARD.decode(data).unwrap();
+    // The runtime strips trailing zeros from return data. There are never more of
+    // them than in an empty quote, and `deserialize` ignores the extra padding.
+    data.resize(data.len() + TAKE_QUOTE_SIZE, 0);
+    TakeQuote::deserialize(&mut data.as_slice()).unwrap()
+}
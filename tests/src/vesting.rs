@@ -0,0 +1,202 @@
+use std::{str::FromStr, thread::sleep, time::Duration};
+
+use anchor_client::{
+    anchor_lang::solana_program::sysvar,
+    solana_sdk::{self, commitment_config::CommitmentConfig, signature::Signer},
+    Cluster,
+};
+use spl_associated_token_account::get_associated_token_address;
+use spl_token::{
+    solana_program::{program_pack::Pack, pubkey::Pubkey},
+    state::Account as TokenAccount,
+    ui_amount_to_amount,
+};
+
+use crate::utils::{initialize, take_offer_accounts, SetupStruct};
+
+use escrow_app::{self, VestingSchedule};
+
+#[tokio::test]
+pub async fn take_vesting_offer_and_claim_vested() {
+    println!("\n//// claim_vested instruction ////");
+
+    // Setup environment: funded accounts, minted tokens, ATAs, balances
+    let SetupStruct {
+        rpc_client,
+        maker,
+        taker,
+        token_mint_a,
+        token_mint_b,
+        token_mint_a_decimals,
+        token_mint_b_decimals,
+        maker_ata_a,
+        taker_ata_b: _,
+    } = initialize().await;
+
+    let program_id = Pubkey::from_str("5gdV4b4cPnnRkVSvBq8WxCxRfyq7i5z9R5scwm3BA4ps").unwrap();
+    let program = anchor_client::Client::new_with_options(
+        Cluster::Localnet,
+        &maker,
+        CommitmentConfig::confirmed(),
+    )
+    .program(program_id)
+    .unwrap();
+
+    let maker_pubkey = maker.pubkey();
+    let taker_pubkey = taker.pubkey();
+
+    // Instruction parameters
+    let offer_id: u64 = 1;
+    let token_a_offered_amount: u64 = ui_amount_to_amount(100.0, token_mint_a_decimals);
+    let token_b_amount_wanted: u64 = ui_amount_to_amount(80.0, token_mint_b_decimals);
+
+    let (offer_pda, _) = Pubkey::find_program_address(
+        &[b"offer", maker_pubkey.as_ref(), &offer_id.to_le_bytes()],
+        &program_id,
+    );
+
+    let vault_ata = get_associated_token_address(&offer_pda, &token_mint_a);
+
+    let taker_ata_a = get_associated_token_address(&taker_pubkey, &token_mint_a);
+
+    let (vesting_pda, _) =
+        Pubkey::find_program_address(&[b"vesting", offer_pda.as_ref()], &program_id);
+    let vesting_vault = get_associated_token_address(&vesting_pda, &token_mint_a);
+
+    ///////////// Make Offer + Vesting (one transaction) /////////////
+    let make_offer_ix = program
+        .request()
+        .accounts(escrow_app::accounts::MakeOffer {
+            maker: maker_pubkey,
+            payer: maker_pubkey,
+            token_mint_a,
+            token_mint_b,
+            maker_token_account_a: maker_ata_a,
+            offer: offer_pda,
+            vault: vault_ata,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::MakeOffer {
+            offer_id,
+            token_a_offered_amount,
+            token_b_amount_wanted,
+            payout: None,
+            hashlock: None,
+        })
+        .instructions()
+        .unwrap()
+        .remove(0);
+
+    program
+        .request()
+        .instruction(make_offer_ix)
+        .accounts(escrow_app::accounts::SetOfferVesting {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferVesting {
+            vesting: Some(VestingSchedule {
+                cliff_seconds: 0,
+                duration_seconds: 2,
+            }),
+        })
+        .send()
+        .await
+        .unwrap();
+
+    // Once the offer is live, the schedule can't change under the taker
+    let result = program
+        .request()
+        .accounts(escrow_app::accounts::SetOfferVesting {
+            owner: maker_pubkey,
+            offer: offer_pda,
+            instructions: sysvar::instructions::ID,
+        })
+        .args(escrow_app::instruction::SetOfferVesting {
+            vesting: Some(VestingSchedule {
+                cliff_seconds: 3600,
+                duration_seconds: 7200,
+            }),
+        })
+        .send()
+        .await;
+
+    assert!(
+        result.is_err(),
+        "Vesting schedule shouldn't change on a live offer"
+    );
+
+    ///////////// Take Offer /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::TakeOffer {
+            vesting: Some(vesting_pda),
+            vesting_vault: Some(vesting_vault),
+            taker_token_account_a: None,
+            ..take_offer_accounts(
+                taker_pubkey,
+                maker_pubkey,
+                offer_pda,
+                token_mint_a,
+                token_mint_b,
+            )
+        })
+        .args(escrow_app::instruction::TakeOffer {
+            preimage: None,
+            token_a_amount: None,
+        })
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("TakeOffer Successful with signature: {}", signature);
+
+    // Assert token A is held in the vesting vault
+    let vesting_vault_account = rpc_client.get_account(&vesting_vault).await.unwrap();
+    let vesting_vault_data = TokenAccount::unpack(&vesting_vault_account.data).unwrap();
+    assert_eq!(vesting_vault_data.amount, token_a_offered_amount);
+
+    // Wait for the end of the schedule
+    sleep(Duration::from_secs(4));
+
+    ///////////// Claim Vested /////////////
+    let signature = program
+        .request()
+        .accounts(escrow_app::accounts::ClaimVested {
+            payer: taker_pubkey,
+            beneficiary: taker_pubkey,
+            rent_payer: taker_pubkey,
+            token_mint: token_mint_a,
+            beneficiary_token_account: taker_ata_a,
+            vesting: vesting_pda,
+            vesting_vault,
+            token_program: spl_token::id(),
+            associated_token_program: spl_associated_token_account::ID,
+            system_program: solana_sdk::system_program::id(),
+        })
+        .args(escrow_app::instruction::ClaimVested {})
+        .signer(taker.insecure_clone())
+        .send()
+        .await
+        .unwrap();
+
+    println!("ClaimVested Successful with signature: {}", signature);
+
+    // Assert taker's token's balance of token mint a
+    let taker_ata_account_a = rpc_client.get_account(&taker_ata_a).await.unwrap();
+    let taker_data_a = TokenAccount::unpack(&taker_ata_account_a.data).unwrap();
+    assert_eq!(taker_data_a.amount, token_a_offered_amount);
+
+    // Asset vesting PDA
+    let vesting_closed = rpc_client.get_account(&vesting_pda).await;
+    assert!(
+        vesting_closed.is_err(),
+        "Vesting account should be closed once fully claimed"
+    );
+
+    println!();
+}